@@ -1,18 +1,19 @@
-#![feature(let_chains)]
-
 use std::cmp::Ordering;
 use std::time::Duration;
 
-use ratatui::crossterm::event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode};
+use ratatui::crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers,
+};
 use ratatui::crossterm::terminal::{EnterAlternateScreen, LeaveAlternateScreen};
 use ratatui::crossterm::{self, terminal};
 use ratatui::layout::{Alignment, Constraint};
 use ratatui::prelude::CrosstermBackend;
 use ratatui::style::{Color, Style};
-use ratatui::widgets::{Block, Paragraph, TableState};
+use ratatui::widgets::{Paragraph, TableState};
 use ratatui::Terminal;
-use ratatui_helpers::stateful_table::{IndexedRow, Padding, StatefulTable, TableStyle, Tabular};
-use ratatui_helpers::view::View;
+use ratatui_helpers::help::HelpView;
+use ratatui_helpers::stateful_table::{IndexedRow, StatefulTable, TableStyle, Tabular};
+use ratatui_helpers::view::{KeyHandler, View};
 use ratatui_helpers::view_controller::ViewController;
 
 #[derive(Default)]
@@ -24,10 +25,11 @@ enum Commands {
     ShowNotification(String),
 }
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Clone)]
 enum ViewKind {
     Main,
     Normal,
+    Help,
 }
 
 #[derive(Clone)]
@@ -72,15 +74,19 @@ impl MainView<'_> {
         (0..100).map(|i| Data(format!("ROW {i}").leak())).collect()
     }
     fn style() -> TableStyle<'static> {
-        TableStyle {
-            table: Style::new(),
-            header: Style::new(),
-            block: (Block::new(), Padding::default()),
-            highlight: Style::new().fg(Color::Red).bg(Color::DarkGray),
-            col_highlight: Style::new(),
-            normal: Style::new(),
-            column_spacing: 5,
-        }
+        TableStyle::default()
+            .highlight(Style::new().fg(Color::Red).bg(Color::DarkGray))
+            .col_highlight(Style::new().fg(Color::Black).bg(Color::Cyan))
+            .zebra(Style::new().bg(Color::Black))
+            .marked(Style::new().fg(Color::Yellow))
+            .changed(Style::new().fg(Color::Green))
+            .added(Style::new().fg(Color::Green))
+            .removed(Style::new().fg(Color::DarkGray))
+            .group(Style::new().fg(Color::Blue))
+            .loading(Style::new().fg(Color::Yellow))
+            .empty(Style::new().fg(Color::DarkGray))
+            .error(Style::new().fg(Color::Red))
+            .column_spacing(5)
     }
 }
 impl View for MainView<'_> {
@@ -91,9 +97,16 @@ impl View for MainView<'_> {
     fn kind(&self) -> Self::Kind {
         ViewKind::Main
     }
+    fn key_handler(&self) -> Option<&dyn KeyHandler> {
+        Some(&self.table)
+    }
     fn draw(&mut self, f: &mut ratatui::Frame<'_>, area: ratatui::prelude::Rect) {
         self.table.draw(f, area);
     }
+    fn tick(&mut self) -> Self::Signal {
+        self.table.tick();
+        Commands::None
+    }
     fn update(&mut self, ev: &event::Event) -> Self::Signal {
         self.table.update(ev);
         if let Event::Key(ev) = ev {
@@ -134,17 +147,22 @@ fn main() {
     let mut term = grab_term();
     let mut ctrl = ViewController::new(Duration::from_millis(1000));
     ctrl.push(Box::new(NormalView));
+    ctrl.set_help_view(
+        KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE),
+        |entries| Box::new(HelpView::new(entries, MainView::style(), ViewKind::Help)),
+    );
 
     while ctrl.is_running() {
         let _ = term.draw(|f| ctrl.draw(f, f.area()));
-        if let Ok(true) = event::poll(Duration::from_millis(200)) {
-            let ev = &event::read().unwrap();
-            match ctrl.curr_mut().update(ev) {
-                Commands::None => {}
-                Commands::QuitView => ctrl.pop(),
-                Commands::OpenMainView => ctrl.push(Box::new(MainView::new())),
-                Commands::ShowNotification(s) => ctrl.show_status(s),
-            }
+        let signal = match event::poll(Duration::from_millis(200)) {
+            Ok(true) => ctrl.update(&event::read().unwrap()),
+            _ => ctrl.tick(),
+        };
+        match signal {
+            Commands::None => {}
+            Commands::QuitView => ctrl.pop(),
+            Commands::OpenMainView => ctrl.push(Box::new(MainView::new())),
+            Commands::ShowNotification(s) => ctrl.show_status(s),
         }
         ctrl.update_status_line();
     }
@@ -0,0 +1,154 @@
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::marker::PhantomData;
+
+use ratatui::crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Alignment, Constraint, Flex, Layout, Rect};
+use ratatui::widgets::TableState;
+use ratatui::Frame;
+use serde::{Deserialize, Serialize};
+
+use crate::keymap::{Command, CommandKeyMap, ShortCut};
+use crate::stateful_table::{StatefulTable, TableStyle, Tabular};
+use crate::table_pane::TablePane;
+use crate::view::{KeyHandler, View};
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Bookmark {
+    pub name: char,
+    pub row: Option<usize>,
+    pub content: String,
+}
+impl Tabular for Bookmark {
+    type Value = char;
+
+    fn cmp_by_col(&self, other: &Self, col: usize) -> Ordering {
+        match col {
+            0 => self.name.cmp(&other.name),
+            1 => self.row.cmp(&other.row),
+            _ => self.content.cmp(&other.content),
+        }
+    }
+    fn value(&self) -> Self::Value {
+        self.name
+    }
+    fn content(&self) -> Vec<String> {
+        vec![
+            self.name.to_string(),
+            self.row.map_or("-".into(), |row| row.to_string()),
+            self.content.clone(),
+        ]
+    }
+    fn column_constraints() -> Vec<fn(u16) -> Constraint> {
+        vec![Constraint::Length, Constraint::Length, Constraint::Fill]
+    }
+    fn column_names() -> Option<Vec<String>> {
+        Some(vec!["Mark".into(), "Row".into(), "Content".into()])
+    }
+    fn column_alignments() -> Option<Vec<Alignment>> {
+        Some(vec![Alignment::Center, Alignment::Right, Alignment::Left])
+    }
+}
+
+pub type JumpFn<'a, S> = Box<dyn Fn(char) -> S + 'a>;
+
+pub struct BookmarksView<'a, M, S, K> {
+    pane: TablePane<'a, Bookmark, BookmarksCommand, S>,
+    on_jump: JumpFn<'a, S>,
+    kind: K,
+    _marker: PhantomData<M>,
+}
+impl<'a, M, S, K> BookmarksView<'a, M, S, K> {
+    pub fn new(
+        bookmarks: Vec<Bookmark>,
+        style: TableStyle<'a>,
+        on_jump: impl Fn(char) -> S + 'a,
+        kind: K,
+    ) -> Self {
+        let state = TableState::new().with_selected(0);
+        let mut table = StatefulTable::new(bookmarks, state, style, Some("Marks".into()));
+        table.set_hyperlinks(false);
+        Self {
+            pane: TablePane::new(table, "Marks"),
+            on_jump: Box::new(on_jump),
+            kind,
+            _marker: PhantomData,
+        }
+    }
+    pub fn pane(&self) -> &TablePane<'a, Bookmark, BookmarksCommand, S> {
+        &self.pane
+    }
+    pub fn pane_mut(&mut self) -> &mut TablePane<'a, Bookmark, BookmarksCommand, S> {
+        &mut self.pane
+    }
+    pub fn set_bookmarks(&mut self, bookmarks: Vec<Bookmark>) {
+        self.pane.table_mut().set_data(bookmarks);
+    }
+}
+impl<M, S, K> View for BookmarksView<'_, M, S, K>
+where
+    S: Default,
+    K: PartialEq + Clone,
+{
+    type Model = M;
+    type Signal = S;
+    type Kind = K;
+
+    fn kind(&self) -> Self::Kind {
+        self.kind.clone()
+    }
+    fn key_handler(&self) -> Option<&dyn KeyHandler> {
+        Some(&self.pane)
+    }
+    fn is_floating(&self) -> bool {
+        true
+    }
+    fn compute_area(&self, area: Rect) -> Rect {
+        let (width, height) = self.pane.table().min_area();
+        let [area] = Layout::horizontal([Constraint::Length(width.max(40))])
+            .flex(Flex::Center)
+            .areas(area);
+        let [area] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(area);
+        area
+    }
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) {
+        self.pane.table_mut().draw(f, area);
+    }
+    fn tick(&mut self) -> Self::Signal {
+        self.pane.tick()
+    }
+    fn update(&mut self, ev: &Event) -> Self::Signal {
+        if let Some(BookmarksCommand::Jump) = self.pane.command(ev) {
+            return match self.pane.table().selected_value() {
+                Some(name) => (self.on_jump)(*name),
+                None => S::default(),
+            };
+        }
+        self.pane.update(ev)
+    }
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BookmarksCommand {
+    Jump,
+}
+impl Display for BookmarksCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BookmarksCommand::Jump => write!(f, "jump to mark"),
+        }
+    }
+}
+impl Command for BookmarksCommand {
+    fn default_bindings() -> Vec<ShortCut<Self>> {
+        vec![ShortCut(
+            BookmarksCommand::Jump,
+            vec![KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE).into()],
+        )]
+    }
+}
+
+pub type BookmarksKeyMap = CommandKeyMap<BookmarksCommand>;
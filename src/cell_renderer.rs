@@ -0,0 +1,56 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Alignment, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::text::Span;
+use ratatui::widgets::{Clear, Gauge, Paragraph, Sparkline, Widget};
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum CellRenderer {
+    Gauge(f64),
+    Sparkline(Vec<u64>),
+    Check(bool),
+    Dot(Color),
+}
+impl CellRenderer {
+    const CHECKED: &'static str = "✓";
+    const UNCHECKED: &'static str = "✗";
+    const DOT: &'static str = "●";
+
+    pub fn render(&self, area: Rect, buf: &mut Buffer, style: Style, alignment: Alignment) {
+        Clear.render(area, buf);
+        buf.set_style(area, style);
+        match self {
+            CellRenderer::Gauge(ratio) => {
+                let ratio = ratio.clamp(0.0, 1.0);
+                Gauge::default()
+                    .ratio(ratio)
+                    .label(format!("{:.0}%", ratio * 100.0))
+                    .gauge_style(style)
+                    .use_unicode(true)
+                    .render(area, buf);
+            }
+            CellRenderer::Sparkline(data) => {
+                Sparkline::default()
+                    .data(data)
+                    .style(style)
+                    .render(area, buf);
+            }
+            CellRenderer::Check(checked) => {
+                let symbol = if *checked {
+                    Self::CHECKED
+                } else {
+                    Self::UNCHECKED
+                };
+                Paragraph::new(symbol)
+                    .style(style)
+                    .alignment(alignment)
+                    .render(area, buf);
+            }
+            CellRenderer::Dot(color) => {
+                Paragraph::new(Span::styled(Self::DOT, style.fg(*color)))
+                    .alignment(alignment)
+                    .render(area, buf);
+            }
+        }
+    }
+}
@@ -0,0 +1,355 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::marker::PhantomData;
+
+use itertools::Itertools;
+use ratatui::crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Alignment, Constraint, Flex, Layout, Rect};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Clear, Paragraph, TableState};
+use ratatui::Frame;
+use serde::{Deserialize, Serialize};
+
+use crate::cell_renderer::CellRenderer;
+use crate::keymap::{Command, CommandKeyMap, ShortCut};
+use crate::query::ColumnKind;
+use crate::stateful_table::{StatefulTable, TableStyle, Tabular};
+use crate::table_pane::TablePane;
+use crate::view::{KeyHandler, View};
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ColumnStats {
+    pub col: usize,
+    // `None` when `Tabular::column_names` has no name for the column
+    pub name: Option<String>,
+    pub kind: ColumnKind,
+    pub count: usize,
+    pub distinct: usize,
+    pub top: Vec<(String, usize)>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub mean: Option<f64>,
+}
+impl ColumnStats {
+    pub fn new(
+        col: usize,
+        name: Option<String>,
+        kind: ColumnKind,
+        values: &[String],
+        top: usize,
+    ) -> Self {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for value in values {
+            *counts.entry(value.as_str()).or_default() += 1;
+        }
+        let distinct = counts.len();
+        let top = counts
+            .into_iter()
+            .sorted_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)))
+            .take(top)
+            .map(|(value, count)| (value.to_string(), count))
+            .collect();
+
+        let kind = match kind {
+            ColumnKind::Text
+                if !values.is_empty()
+                    && values.iter().all(|v| ColumnKind::Number.parse(v).is_some()) =>
+            {
+                ColumnKind::Number
+            }
+            kind => kind,
+        };
+        let numbers = values.iter().filter_map(|v| kind.parse(v)).collect_vec();
+        let (min, max, mean) = if numbers.is_empty() {
+            (None, None, None)
+        } else {
+            let min = numbers.iter().copied().min_by(|a, b| a.total_cmp(b));
+            let max = numbers.iter().copied().max_by(|a, b| a.total_cmp(b));
+            let mean = numbers.iter().sum::<f64>() / numbers.len() as f64;
+            (min, max, Some(mean))
+        };
+
+        Self {
+            col,
+            name,
+            kind,
+            count: values.len(),
+            distinct,
+            top,
+            min,
+            max,
+            mean,
+        }
+    }
+    pub fn is_numeric(&self) -> bool {
+        self.mean.is_some()
+    }
+    pub fn title(&self) -> String {
+        let name = self
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("#{}", self.col));
+        format!("{name} ({})", self.kind)
+    }
+    // `None` for unnamed columns and names or values with quotes, the query
+    // language can't express them
+    pub fn query_for(&self, value: &str) -> Option<String> {
+        let name = self.name.as_ref()?;
+        if name.contains('"') || value.contains('"') {
+            return None;
+        }
+        let name = match name.contains(|c: char| c.is_whitespace() || "():=!<>~|-".contains(c)) {
+            true => format!("\"{name}\""),
+            false => name.clone(),
+        };
+        Some(match self.kind {
+            ColumnKind::Text => format!("{name}=\"{value}\""),
+            _ if value.is_empty() || value.contains(char::is_whitespace) => {
+                format!("{name}=\"{value}\"")
+            }
+            _ => format!("{name}={value}"),
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct TopValue {
+    value: String,
+    count: usize,
+    share: f64,
+}
+impl Tabular for TopValue {
+    type Value = String;
+
+    fn cmp_by_col(&self, other: &Self, col: usize) -> Ordering {
+        match col {
+            0 => self.value.cmp(&other.value),
+            _ => self.count.cmp(&other.count),
+        }
+    }
+    fn value(&self) -> Self::Value {
+        self.value.clone()
+    }
+    fn content(&self) -> Vec<String> {
+        vec![
+            self.value.clone(),
+            self.count.to_string(),
+            format!("{:.0}%", self.share * 100.0),
+        ]
+    }
+    fn column_constraints() -> Vec<fn(u16) -> Constraint> {
+        vec![Constraint::Fill, Constraint::Length, |_| {
+            Constraint::Length(12)
+        }]
+    }
+    fn column_names() -> Option<Vec<String>> {
+        Some(vec!["Value".into(), "Count".into(), "Share".into()])
+    }
+    fn column_alignments() -> Option<Vec<Alignment>> {
+        Some(vec![Alignment::Left, Alignment::Right, Alignment::Left])
+    }
+    fn cell_renderer(&self, col: usize) -> Option<CellRenderer> {
+        (col == 2).then_some(CellRenderer::Gauge(self.share))
+    }
+}
+
+pub type SelectFn<'a, S> = Box<dyn Fn(String) -> S + 'a>;
+
+pub struct ColumnStatsView<'a, M, S, K> {
+    stats: ColumnStats,
+    pane: TablePane<'a, TopValue, StatsCommand, S>,
+    block: Block<'a>,
+    on_select: SelectFn<'a, S>,
+    kind: K,
+    _marker: PhantomData<M>,
+}
+impl<'a, M, S, K> ColumnStatsView<'a, M, S, K> {
+    pub fn new(
+        stats: ColumnStats,
+        style: TableStyle<'a>,
+        on_select: impl Fn(String) -> S + 'a,
+        kind: K,
+    ) -> Self {
+        let block = style.block.0.clone();
+        let top = stats
+            .top
+            .iter()
+            .map(|(value, count)| TopValue {
+                value: value.clone(),
+                count: *count,
+                share: *count as f64 / stats.count.max(1) as f64,
+            })
+            .collect();
+        let mut table = StatefulTable::new(top, TableState::new().with_selected(0), style, None);
+        table.set_hyperlinks(false);
+        Self {
+            stats,
+            pane: TablePane::new(table, "Column stats"),
+            block,
+            on_select: Box::new(on_select),
+            kind,
+            _marker: PhantomData,
+        }
+    }
+    pub fn stats(&self) -> &ColumnStats {
+        &self.stats
+    }
+    pub fn pane(&self) -> &TablePane<'a, TopValue, StatsCommand, S> {
+        &self.pane
+    }
+    pub fn pane_mut(&mut self) -> &mut TablePane<'a, TopValue, StatsCommand, S> {
+        &mut self.pane
+    }
+    pub fn selected_query(&self) -> Option<String> {
+        let value = self.pane.table().selected_value()?;
+        self.stats.query_for(value)
+    }
+
+    // --- helpers
+    fn summary(&self) -> Vec<Line<'static>> {
+        let stats = &self.stats;
+        let mut lines = vec![Line::from(format!(
+            "count: {}  distinct: {}",
+            stats.count, stats.distinct
+        ))];
+        if let (Some(min), Some(max), Some(mean)) = (stats.min, stats.max, stats.mean) {
+            lines.push(Line::from(format!(
+                "min: {}  max: {}  mean: {}",
+                fmt_number(min),
+                fmt_number(max),
+                fmt_number(mean)
+            )));
+        }
+        lines
+    }
+}
+impl<M, S, K> View for ColumnStatsView<'_, M, S, K>
+where
+    S: Default,
+    K: PartialEq + Clone,
+{
+    type Model = M;
+    type Signal = S;
+    type Kind = K;
+
+    fn kind(&self) -> Self::Kind {
+        self.kind.clone()
+    }
+    fn key_handler(&self) -> Option<&dyn KeyHandler> {
+        Some(&self.pane)
+    }
+    fn is_floating(&self) -> bool {
+        true
+    }
+    fn compute_area(&self, area: Rect) -> Rect {
+        let (width, height) = self.pane.table().min_area();
+        let width = width.max(40).saturating_add(2);
+        let height = height
+            .saturating_add(self.summary().len() as u16)
+            .saturating_add(3);
+        let [area] = Layout::horizontal([Constraint::Length(width)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [area] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(area);
+        area
+    }
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) {
+        let block = self.block.clone().title(self.stats.title());
+        let inner = block.inner(area);
+        f.render_widget(Clear, area);
+        f.render_widget(block, area);
+
+        let summary = self.summary();
+        let [summary_area, _, table_area] = Layout::vertical([
+            Constraint::Length(summary.len() as u16),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+        ])
+        .areas(inner);
+        f.render_widget(Paragraph::new(summary), summary_area);
+        self.pane.table_mut().draw(f, table_area);
+    }
+    fn tick(&mut self) -> Self::Signal {
+        self.pane.tick()
+    }
+    fn update(&mut self, ev: &Event) -> Self::Signal {
+        if let Some(StatsCommand::ApplyFilter) = self.pane.command(ev) {
+            return match self.selected_query() {
+                Some(query) => (self.on_select)(query),
+                None => S::default(),
+            };
+        }
+        self.pane.update(ev)
+    }
+}
+
+fn fmt_number(n: f64) -> String {
+    if n.fract() == 0.0 {
+        format!("{n:.0}")
+    } else {
+        format!("{n:.2}")
+    }
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatsCommand {
+    ApplyFilter,
+}
+impl Display for StatsCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StatsCommand::ApplyFilter => write!(f, "filter by value"),
+        }
+    }
+}
+impl Command for StatsCommand {
+    fn default_bindings() -> Vec<ShortCut<Self>> {
+        vec![ShortCut(
+            StatsCommand::ApplyFilter,
+            vec![KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE).into()],
+        )]
+    }
+    fn description(&self) -> Option<String> {
+        let description = match self {
+            StatsCommand::ApplyFilter => "filter the table by the selected value",
+        };
+        Some(description.into())
+    }
+}
+
+pub type StatsKeyMap = CommandKeyMap<StatsCommand>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::Query;
+
+    fn stats(name: Option<&str>, kind: ColumnKind) -> ColumnStats {
+        ColumnStats::new(0, name.map(String::from), kind, &["x".into()], 5)
+    }
+
+    #[test]
+    fn builds_queries_the_parser_accepts() {
+        let names = ["first name".to_string()];
+        for (kind, value, query) in [
+            (ColumnKind::Text, "ann lee", "\"first name\"=\"ann lee\""),
+            (ColumnKind::Number, "", "\"first name\"=\"\""),
+            (ColumnKind::Number, "3", "\"first name\"=3"),
+        ] {
+            let built = stats(Some("first name"), kind).query_for(value);
+            assert_eq!(built.as_deref(), Some(query));
+            assert!(Query::parse(query, &names, &[kind]).is_ok());
+        }
+    }
+
+    #[test]
+    fn skips_queries_for_unnamed_columns() {
+        let stats = stats(None, ColumnKind::Text);
+        assert_eq!(stats.query_for("x"), None);
+        assert_eq!(stats.title(), "#0 (text)");
+    }
+}
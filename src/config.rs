@@ -1,7 +1,9 @@
 use std::fs;
 
 use directories::ProjectDirs;
+use ratatui::style::{Color, Modifier, Style};
 use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
 
 pub fn parse_toml<Partial: DeserializeOwned, Full: From<Partial>>(proj: &str, path: &str) -> Full {
     let proj = ProjectDirs::from("", "", proj).expect("Failed to find the project directory");
@@ -18,3 +20,22 @@ pub fn parse_toml<Partial: DeserializeOwned, Full: From<Partial>>(proj: &str, pa
         }
     }
 }
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct StyleDef {
+    fg: Option<Color>,
+    bg: Option<Color>,
+    add_modifier: Modifier,
+    sub_modifier: Modifier,
+}
+
+pub fn deserialize_style<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Style, D::Error> {
+    let def = StyleDef::deserialize(deserializer)?;
+    let mut style = Style::new()
+        .add_modifier(def.add_modifier)
+        .remove_modifier(def.sub_modifier);
+    style.fg = def.fg;
+    style.bg = def.bg;
+    Ok(style)
+}
@@ -42,6 +42,9 @@ where
         }
     }
     pub fn draw(&mut self, f: &mut Frame, area: Rect) {
+        if area.is_empty() {
+            return;
+        }
         self.view.draw(f, area);
     }
 }
@@ -0,0 +1,186 @@
+use std::marker::PhantomData;
+
+use ratatui::crossterm::event::Event;
+use ratatui::layout::{Alignment, Constraint, Flex, Layout, Rect};
+use ratatui::style::{Style, Stylize};
+use ratatui::widgets::TableState;
+use ratatui::Frame;
+
+use crate::keymap::{Command, KeyMap};
+use crate::stateful_table::{StatefulTable, TableStyle, Tabular};
+use crate::view::View;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct HelpEntry {
+    pub section: String,
+    pub command: String,
+    pub keys: String,
+    pub description: String,
+}
+
+pub fn help_entries<K>(section: &str, keymap: &K) -> Vec<HelpEntry>
+where
+    K: KeyMap,
+    K::Command: Command,
+{
+    keymap
+        .get_shortcuts()
+        .iter()
+        .map(|shortcut| {
+            let content = shortcut.content();
+            HelpEntry {
+                section: section.to_string(),
+                command: content[0].clone(),
+                keys: content[1].clone(),
+                description: shortcut.0.description().unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+#[derive(Clone)]
+enum HelpRow {
+    Section(String),
+    Entry(HelpEntry),
+}
+impl Tabular for HelpRow {
+    type Value = Option<String>;
+
+    fn value(&self) -> Self::Value {
+        match self {
+            HelpRow::Section(_) => None,
+            HelpRow::Entry(entry) => Some(entry.command.clone()),
+        }
+    }
+    fn content(&self) -> Vec<String> {
+        match self {
+            HelpRow::Section(section) => vec![section.clone(), String::new(), String::new()],
+            HelpRow::Entry(entry) => vec![
+                format!("  {}", entry.command),
+                entry.keys.clone(),
+                entry.description.clone(),
+            ],
+        }
+    }
+    fn style(&self) -> Style {
+        match self {
+            HelpRow::Section(_) => Style::new().bold(),
+            HelpRow::Entry(_) => Style::new(),
+        }
+    }
+    fn column_constraints() -> Vec<fn(u16) -> Constraint> {
+        vec![Constraint::Length, Constraint::Length, Constraint::Fill]
+    }
+    fn column_names() -> Option<Vec<String>> {
+        Some(vec!["Command".into(), "Key".into(), "Description".into()])
+    }
+    fn column_alignments() -> Option<Vec<Alignment>> {
+        Some(vec![Alignment::Left, Alignment::Left, Alignment::Left])
+    }
+}
+
+pub struct HelpView<'a, M, S, K> {
+    entries: Vec<HelpEntry>,
+    table: StatefulTable<'a, HelpRow>,
+    filter: String,
+    kind: K,
+    _marker: PhantomData<(M, S)>,
+}
+impl<'a, M, S, K> HelpView<'a, M, S, K> {
+    pub fn new(entries: Vec<HelpEntry>, style: TableStyle<'a>, kind: K) -> Self {
+        let state = TableState::new().with_selected(0);
+        let mut table = StatefulTable::new(vec![], state, style, Some("Help".into()));
+        table.set_hyperlinks(false);
+        let mut help = Self {
+            entries,
+            table,
+            filter: String::new(),
+            kind,
+            _marker: PhantomData,
+        };
+        help.refresh();
+        help
+    }
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+    pub fn set_filter(&mut self, filter: &str) {
+        self.filter = filter.trim().to_lowercase();
+        self.refresh();
+    }
+
+    // --- helpers
+    fn refresh(&mut self) {
+        let mut rows = vec![];
+        let mut sections: Vec<&str> = vec![];
+        for entry in &self.entries {
+            if !sections.contains(&entry.section.as_str()) {
+                sections.push(&entry.section);
+            }
+        }
+        for section in sections {
+            let entries = self
+                .entries
+                .iter()
+                .filter(|e| e.section == section)
+                .filter(|e| e.command.to_lowercase().contains(&self.filter))
+                .map(|e| HelpRow::Entry(e.clone()))
+                .collect::<Vec<_>>();
+            if !entries.is_empty() {
+                rows.push(HelpRow::Section(section.to_string()));
+                rows.extend(entries);
+            }
+        }
+        self.table.set_data(rows);
+        let title = match self.filter.as_str() {
+            "" => "Help".to_string(),
+            filter => format!("Help [{filter}]"),
+        };
+        self.table.set_title(Some(title));
+    }
+}
+impl<M, S, K> View for HelpView<'_, M, S, K>
+where
+    S: Default,
+    K: PartialEq + Clone,
+{
+    type Model = M;
+    type Signal = S;
+    type Kind = K;
+
+    fn kind(&self) -> Self::Kind {
+        self.kind.clone()
+    }
+    fn is_floating(&self) -> bool {
+        true
+    }
+    fn compute_area(&self, area: Rect) -> Rect {
+        let (width, height) = self.table.min_area();
+        let [area] = Layout::horizontal([Constraint::Length(width.max(50))])
+            .flex(Flex::Center)
+            .areas(area);
+        let [area] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(area);
+        area
+    }
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) {
+        self.table.draw(f, area);
+    }
+    fn tick(&mut self) -> Self::Signal {
+        self.table.tick();
+        S::default()
+    }
+    fn update(&mut self, ev: &Event) -> Self::Signal {
+        self.table.update(ev);
+        S::default()
+    }
+    fn on_prompt_change(&mut self, value: String) -> Self::Signal {
+        self.set_filter(&value);
+        S::default()
+    }
+    fn on_prompt_submit(&mut self, value: String) -> Self::Signal {
+        self.set_filter(&value);
+        S::default()
+    }
+}
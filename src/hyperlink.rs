@@ -0,0 +1,75 @@
+use std::env;
+use std::process::{Command, Stdio};
+
+use itertools::Itertools;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+
+pub fn osc8(url: &str, text: &str) -> String {
+    format!("\x1b]8;;{url}\x07{text}\x1b]8;;\x07")
+}
+
+pub fn is_supported() -> bool {
+    let var = |name: &str| env::var(name).unwrap_or_default();
+    if let Ok(value) = env::var("FORCE_HYPERLINK") {
+        return value != "0";
+    }
+    if var("TERM") == "dumb" || env::var("TMUX").is_ok() {
+        return false;
+    }
+    let term_program = var("TERM_PROGRAM");
+    ["iTerm.app", "WezTerm", "vscode", "ghostty", "Hyper"].contains(&term_program.as_str())
+        || var("VTE_VERSION").parse::<u32>().is_ok_and(|v| v >= 5000)
+        || env::var("KITTY_WINDOW_ID").is_ok()
+        || env::var("WT_SESSION").is_ok()
+        || env::var("KONSOLE_VERSION").is_ok()
+        || ["kitty", "alacritty", "foot", "wezterm"]
+            .iter()
+            .any(|t| var("TERM").contains(t))
+}
+
+// the text is re-emitted in chunks of 2 cells, since the backend computes the
+// width of the escape sequence as the width of its visible text
+pub fn render(area: Rect, buf: &mut Buffer, url: &str) {
+    for y in area.top()..area.bottom() {
+        let symbols = (area.left()..area.right())
+            .map(|x| (x, buf[(x, y)].symbol().to_string()))
+            .collect_vec();
+        let Some(start) = symbols.iter().position(|(_, s)| s.trim() != "") else {
+            continue;
+        };
+        let end = symbols
+            .iter()
+            .rposition(|(_, s)| s.trim() != "")
+            .unwrap_or(start);
+        for chunk in &symbols[start..=end].iter().chunks(2) {
+            let chunk = chunk.collect_vec();
+            let text = chunk.iter().map(|(_, s)| s.as_str()).collect::<String>();
+            buf[(chunk[0].0, y)].set_symbol(&osc8(url, &text));
+        }
+    }
+}
+
+pub fn default_opener() -> Vec<String> {
+    if cfg!(target_os = "macos") {
+        vec!["open".into()]
+    } else if cfg!(target_os = "windows") {
+        vec!["cmd".into(), "/C".into(), "start".into(), String::new()]
+    } else {
+        vec!["xdg-open".into()]
+    }
+}
+
+pub fn open(opener: &[String], target: &str) -> std::io::Result<()> {
+    let Some((program, args)) = opener.split_first() else {
+        return Ok(());
+    };
+    Command::new(program)
+        .args(args)
+        .arg(target)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+}
@@ -0,0 +1,738 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::marker::PhantomData;
+
+use itertools::Itertools;
+use ratatui::crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Alignment, Constraint, Rect};
+use ratatui::widgets::TableState;
+use ratatui::Frame;
+use serde::{ser, Deserialize, Serialize};
+
+use crate::clipboard;
+use crate::keymap::{Command, CommandKeyMap, ShortCut};
+use crate::stateful_table::{StatefulTable, TableStyle, Tabular};
+use crate::table_pane::TablePane;
+use crate::view::{KeyHandler, View};
+
+const EXPANDED: char = '▾';
+const COLLAPSED: char = '▸';
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Property {
+    path: String,
+    depth: usize,
+    value: String,
+    children: Option<usize>,
+    collapsed: bool,
+}
+impl Property {
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+    pub fn display_value(&self) -> &str {
+        &self.value
+    }
+    pub fn is_container(&self) -> bool {
+        self.children.is_some()
+    }
+    // the paths of the containers above, `a.b[0]` is under `a.b` and `a`
+    fn ancestors(&self) -> impl Iterator<Item = &str> {
+        self.path
+            .match_indices(['.', '['])
+            .filter(|(idx, _)| *idx > 0)
+            .map(|(idx, _)| &self.path[..idx])
+    }
+}
+impl Tabular for Property {
+    type Value = String;
+
+    fn cmp_by_col(&self, other: &Self, col: usize) -> Ordering {
+        match col {
+            0 => self.path.cmp(&other.path),
+            _ => match (self.value.parse::<f64>(), other.value.parse::<f64>()) {
+                (Ok(a), Ok(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+                _ => self.value.cmp(&other.value),
+            },
+        }
+    }
+    fn value(&self) -> Self::Value {
+        self.path.clone()
+    }
+    fn content(&self) -> Vec<String> {
+        let marker = match (self.children, self.collapsed) {
+            (None, _) => ' ',
+            (Some(_), false) => EXPANDED,
+            (Some(_), true) => COLLAPSED,
+        };
+        vec![
+            format!("{}{marker} {}", "  ".repeat(self.depth), self.path),
+            self.value.clone(),
+        ]
+    }
+    fn column_constraints() -> Vec<fn(u16) -> Constraint> {
+        vec![Constraint::Length, Constraint::Fill]
+    }
+    fn column_names() -> Option<Vec<String>> {
+        Some(vec!["Key".into(), "Value".into()])
+    }
+    fn column_alignments() -> Option<Vec<Alignment>> {
+        Some(vec![Alignment::Left, Alignment::Left])
+    }
+}
+
+pub struct Inspector<'a, M, S, K> {
+    pane: TablePane<'a, Property, InspectorCommand, S>,
+    properties: Vec<Property>,
+    collapsed: HashSet<String>,
+    filter: String,
+    kind: K,
+    _marker: PhantomData<(M, S)>,
+}
+impl<'a, M, S, K> Inspector<'a, M, S, K> {
+    pub fn new<T: Serialize>(
+        value: &T,
+        style: TableStyle<'a>,
+        title: Option<String>,
+        kind: K,
+    ) -> Result<Self, InspectError> {
+        let mut inspector = Self {
+            pane: TablePane::new(
+                StatefulTable::new(vec![], TableState::new(), style, title),
+                "Inspector",
+            ),
+            properties: vec![],
+            collapsed: HashSet::new(),
+            filter: String::new(),
+            kind,
+            _marker: PhantomData,
+        };
+        inspector.set_value(value)?;
+        Ok(inspector)
+    }
+    pub fn pane(&self) -> &TablePane<'a, Property, InspectorCommand, S> {
+        &self.pane
+    }
+    pub fn pane_mut(&mut self) -> &mut TablePane<'a, Property, InspectorCommand, S> {
+        &mut self.pane
+    }
+    pub fn set_value<T: Serialize>(&mut self, value: &T) -> Result<(), InspectError> {
+        let node = value.serialize(NodeSerializer)?;
+        self.properties.clear();
+        match &node {
+            Node::Leaf(_) => flatten(&node, String::new(), 0, &mut self.properties),
+            _ => flatten_children(&node, "", 0, &mut self.properties),
+        }
+        self.refresh();
+        Ok(())
+    }
+    pub fn selected_property(&self) -> Option<&Property> {
+        let path = self.pane.table().selected_value()?;
+        self.properties.iter().find(|p| &p.path == path)
+    }
+    pub fn toggle(&mut self) {
+        let Some(property) = self.selected_property().filter(|p| p.is_container()) else {
+            return;
+        };
+        let path = property.path.clone();
+        if !self.collapsed.remove(&path) {
+            self.collapsed.insert(path);
+        }
+        self.refresh();
+    }
+    pub fn expand_all(&mut self) {
+        self.collapsed.clear();
+        self.refresh();
+    }
+    pub fn collapse_all(&mut self) {
+        self.collapsed = self
+            .properties
+            .iter()
+            .filter(|p| p.is_container())
+            .map(|p| p.path.clone())
+            .collect();
+        self.refresh();
+    }
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+    pub fn set_filter(&mut self, filter: &str) {
+        self.filter = filter.trim().to_lowercase();
+        self.refresh();
+    }
+    pub fn copy_selected(&self) -> std::io::Result<()> {
+        match self.selected_property() {
+            Some(property) => clipboard::copy(&property.value),
+            None => Ok(()),
+        }
+    }
+
+    // --- helpers
+    fn refresh(&mut self) {
+        let selected = self.pane.table().selected_value().cloned();
+        // matching properties along with the containers leading to them
+        let matching: HashSet<&str> = self
+            .properties
+            .iter()
+            .filter(|p| !self.filter.is_empty() && p.path.to_lowercase().contains(&self.filter))
+            .flat_map(|p| p.ancestors().chain([p.path.as_str()]))
+            .collect();
+        let visible = self
+            .properties
+            .iter()
+            .filter(|p| {
+                if self.filter.is_empty() {
+                    !p.ancestors().any(|a| self.collapsed.contains(a))
+                } else {
+                    matching.contains(p.path.as_str())
+                }
+            })
+            .map(|p| Property {
+                collapsed: self.filter.is_empty() && self.collapsed.contains(&p.path),
+                ..p.clone()
+            })
+            .collect_vec();
+        self.pane.table_mut().set_data(visible);
+        if let Some(row) = selected.and_then(|path| {
+            (0..self.pane.table().rows_count())
+                .find(|row| self.pane.table().value_at(*row) == Some(&path))
+        }) {
+            self.pane.table_mut().select_absolute(row);
+        }
+    }
+}
+impl<M, S, K> View for Inspector<'_, M, S, K>
+where
+    S: Default,
+    K: PartialEq + Clone,
+{
+    type Model = M;
+    type Signal = S;
+    type Kind = K;
+
+    fn kind(&self) -> Self::Kind {
+        self.kind.clone()
+    }
+    fn key_handler(&self) -> Option<&dyn KeyHandler> {
+        Some(&self.pane)
+    }
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) {
+        self.pane.table_mut().draw(f, area);
+    }
+    fn tick(&mut self) -> Self::Signal {
+        self.pane.tick()
+    }
+    fn update(&mut self, ev: &Event) -> Self::Signal {
+        if let Some(cmd) = self.pane.command(ev) {
+            match cmd {
+                InspectorCommand::Toggle => self.toggle(),
+                InspectorCommand::ExpandAll => self.expand_all(),
+                InspectorCommand::CollapseAll => self.collapse_all(),
+                InspectorCommand::CopyValue => {
+                    let _ = self.copy_selected();
+                }
+            }
+            return S::default();
+        }
+        self.pane.update(ev)
+    }
+    fn on_prompt_change(&mut self, value: String) -> Self::Signal {
+        self.set_filter(&value);
+        S::default()
+    }
+    fn on_prompt_submit(&mut self, value: String) -> Self::Signal {
+        self.set_filter(&value);
+        S::default()
+    }
+}
+
+fn flatten(node: &Node, path: String, depth: usize, out: &mut Vec<Property>) {
+    let (summary, children) = match node {
+        Node::Leaf(value) => (value.clone(), None),
+        Node::Map(entries) => (format!("{{{}}}", entries.len()), Some(entries.len())),
+        Node::Seq(items) => (format!("[{}]", items.len()), Some(items.len())),
+    };
+    out.push(Property {
+        path: path.clone(),
+        depth,
+        value: summary,
+        children,
+        collapsed: false,
+    });
+    flatten_children(node, &path, depth + 1, out);
+}
+
+fn flatten_children(node: &Node, path: &str, depth: usize, out: &mut Vec<Property>) {
+    match node {
+        Node::Map(entries) => {
+            for (key, node) in entries {
+                let key = if !key.is_empty()
+                    && key
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+                {
+                    key.clone()
+                } else {
+                    format!("{key:?}")
+                };
+                let path = match path {
+                    "" => key,
+                    _ => format!("{path}.{key}"),
+                };
+                flatten(node, path, depth, out);
+            }
+        }
+        Node::Seq(items) => {
+            for (idx, node) in items.iter().enumerate() {
+                flatten(node, format!("{path}[{idx}]"), depth, out);
+            }
+        }
+        Node::Leaf(_) => {}
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct InspectError {
+    pub msg: String,
+}
+impl Display for InspectError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.msg)
+    }
+}
+impl std::error::Error for InspectError {}
+impl ser::Error for InspectError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+// the serialized value, with fields and entries in the order they were written
+enum Node {
+    Leaf(String),
+    Map(Vec<(String, Node)>),
+    Seq(Vec<Node>),
+}
+
+struct NodeSerializer;
+
+impl NodeSerializer {
+    fn leaf(value: impl Display) -> Result<Node, InspectError> {
+        Ok(Node::Leaf(value.to_string()))
+    }
+}
+impl ser::Serializer for NodeSerializer {
+    type Ok = Node;
+    type Error = InspectError;
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = SeqSerializer;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = MapSerializer;
+    type SerializeStructVariant = MapSerializer;
+
+    fn serialize_bool(self, v: bool) -> Result<Node, InspectError> {
+        Self::leaf(v)
+    }
+    fn serialize_i8(self, v: i8) -> Result<Node, InspectError> {
+        Self::leaf(v)
+    }
+    fn serialize_i16(self, v: i16) -> Result<Node, InspectError> {
+        Self::leaf(v)
+    }
+    fn serialize_i32(self, v: i32) -> Result<Node, InspectError> {
+        Self::leaf(v)
+    }
+    fn serialize_i64(self, v: i64) -> Result<Node, InspectError> {
+        Self::leaf(v)
+    }
+    fn serialize_i128(self, v: i128) -> Result<Node, InspectError> {
+        Self::leaf(v)
+    }
+    fn serialize_u8(self, v: u8) -> Result<Node, InspectError> {
+        Self::leaf(v)
+    }
+    fn serialize_u16(self, v: u16) -> Result<Node, InspectError> {
+        Self::leaf(v)
+    }
+    fn serialize_u32(self, v: u32) -> Result<Node, InspectError> {
+        Self::leaf(v)
+    }
+    fn serialize_u64(self, v: u64) -> Result<Node, InspectError> {
+        Self::leaf(v)
+    }
+    fn serialize_u128(self, v: u128) -> Result<Node, InspectError> {
+        Self::leaf(v)
+    }
+    fn serialize_f32(self, v: f32) -> Result<Node, InspectError> {
+        Self::leaf(format!("{v:?}"))
+    }
+    fn serialize_f64(self, v: f64) -> Result<Node, InspectError> {
+        Self::leaf(format!("{v:?}"))
+    }
+    fn serialize_char(self, v: char) -> Result<Node, InspectError> {
+        Self::leaf(v)
+    }
+    fn serialize_str(self, v: &str) -> Result<Node, InspectError> {
+        Self::leaf(v)
+    }
+    fn serialize_bytes(self, v: &[u8]) -> Result<Node, InspectError> {
+        Ok(Node::Seq(
+            v.iter().map(|b| Node::Leaf(b.to_string())).collect(),
+        ))
+    }
+    fn serialize_none(self) -> Result<Node, InspectError> {
+        Self::leaf("null")
+    }
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Node, InspectError> {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<Node, InspectError> {
+        Self::leaf("null")
+    }
+    fn serialize_unit_struct(self, name: &'static str) -> Result<Node, InspectError> {
+        Self::leaf(name)
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<Node, InspectError> {
+        Self::leaf(variant)
+    }
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Node, InspectError> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Node, InspectError> {
+        Ok(Node::Map(vec![(variant.into(), value.serialize(self)?)]))
+    }
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqSerializer, InspectError> {
+        Ok(SeqSerializer {
+            variant: None,
+            items: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+    fn serialize_tuple(self, len: usize) -> Result<SeqSerializer, InspectError> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SeqSerializer, InspectError> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SeqSerializer, InspectError> {
+        Ok(SeqSerializer {
+            variant: Some(variant),
+            items: Vec::with_capacity(len),
+        })
+    }
+    fn serialize_map(self, len: Option<usize>) -> Result<MapSerializer, InspectError> {
+        Ok(MapSerializer {
+            variant: None,
+            key: None,
+            entries: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<MapSerializer, InspectError> {
+        self.serialize_map(Some(len))
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<MapSerializer, InspectError> {
+        Ok(MapSerializer {
+            variant: Some(variant),
+            key: None,
+            entries: Vec::with_capacity(len),
+        })
+    }
+}
+
+// wraps the node of an enum variant in a map keyed by the variant's name
+fn in_variant(variant: Option<&'static str>, node: Node) -> Node {
+    match variant {
+        Some(variant) => Node::Map(vec![(variant.into(), node)]),
+        None => node,
+    }
+}
+
+struct SeqSerializer {
+    variant: Option<&'static str>,
+    items: Vec<Node>,
+}
+impl ser::SerializeSeq for SeqSerializer {
+    type Ok = Node;
+    type Error = InspectError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), InspectError> {
+        self.items.push(value.serialize(NodeSerializer)?);
+        Ok(())
+    }
+    fn end(self) -> Result<Node, InspectError> {
+        Ok(in_variant(self.variant, Node::Seq(self.items)))
+    }
+}
+impl ser::SerializeTuple for SeqSerializer {
+    type Ok = Node;
+    type Error = InspectError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), InspectError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+    fn end(self) -> Result<Node, InspectError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+impl ser::SerializeTupleStruct for SeqSerializer {
+    type Ok = Node;
+    type Error = InspectError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), InspectError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+    fn end(self) -> Result<Node, InspectError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+impl ser::SerializeTupleVariant for SeqSerializer {
+    type Ok = Node;
+    type Error = InspectError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), InspectError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+    fn end(self) -> Result<Node, InspectError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+struct MapSerializer {
+    variant: Option<&'static str>,
+    key: Option<String>,
+    entries: Vec<(String, Node)>,
+}
+impl ser::SerializeMap for MapSerializer {
+    type Ok = Node;
+    type Error = InspectError;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), InspectError> {
+        match key.serialize(NodeSerializer)? {
+            Node::Leaf(key) => {
+                self.key = Some(key);
+                Ok(())
+            }
+            _ => Err(ser::Error::custom("map keys must be scalars")),
+        }
+    }
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), InspectError> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| <InspectError as ser::Error>::custom("map value without a key"))?;
+        self.entries.push((key, value.serialize(NodeSerializer)?));
+        Ok(())
+    }
+    fn end(self) -> Result<Node, InspectError> {
+        Ok(in_variant(self.variant, Node::Map(self.entries)))
+    }
+}
+impl ser::SerializeStruct for MapSerializer {
+    type Ok = Node;
+    type Error = InspectError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), InspectError> {
+        self.entries
+            .push((key.into(), value.serialize(NodeSerializer)?));
+        Ok(())
+    }
+    fn end(self) -> Result<Node, InspectError> {
+        ser::SerializeMap::end(self)
+    }
+}
+impl ser::SerializeStructVariant for MapSerializer {
+    type Ok = Node;
+    type Error = InspectError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), InspectError> {
+        ser::SerializeStruct::serialize_field(self, key, value)
+    }
+    fn end(self) -> Result<Node, InspectError> {
+        ser::SerializeMap::end(self)
+    }
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InspectorCommand {
+    Toggle,
+    ExpandAll,
+    CollapseAll,
+    CopyValue,
+}
+impl Display for InspectorCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InspectorCommand::Toggle => write!(f, "expand/collapse"),
+            InspectorCommand::ExpandAll => write!(f, "expand all"),
+            InspectorCommand::CollapseAll => write!(f, "collapse all"),
+            InspectorCommand::CopyValue => write!(f, "copy value"),
+        }
+    }
+}
+impl Command for InspectorCommand {
+    fn default_bindings() -> Vec<ShortCut<Self>> {
+        vec![
+            ShortCut(
+                InspectorCommand::Toggle,
+                vec![KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE).into()],
+            ),
+            ShortCut(
+                InspectorCommand::ExpandAll,
+                vec![KeyEvent::new(KeyCode::Char('+'), KeyModifiers::NONE).into()],
+            ),
+            ShortCut(
+                InspectorCommand::CollapseAll,
+                vec![KeyEvent::new(KeyCode::Char('-'), KeyModifiers::NONE).into()],
+            ),
+            ShortCut(
+                InspectorCommand::CopyValue,
+                vec![KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE).into()],
+            ),
+        ]
+    }
+}
+
+pub type InspectorKeyMap = CommandKeyMap<InspectorCommand>;
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use super::*;
+
+    fn properties<T: Serialize>(value: &T) -> Vec<(String, String)> {
+        let inspector: Inspector<(), (), ()> =
+            Inspector::new(value, TableStyle::default(), None, ()).unwrap();
+        inspector
+            .properties
+            .iter()
+            .map(|p| (p.path.clone(), p.value.clone()))
+            .collect()
+    }
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected
+            .iter()
+            .map(|(p, v)| (p.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[derive(Serialize)]
+    struct Server {
+        name: String,
+        port: Option<u16>,
+        tags: Vec<Option<&'static str>>,
+        id: u64,
+    }
+
+    #[test]
+    fn shows_the_containers_above_matches() {
+        let value = BTreeMap::from([
+            ("a", BTreeMap::from([("b", 1), ("c", 2)])),
+            ("d", BTreeMap::new()),
+        ]);
+        let mut inspector: Inspector<(), (), ()> =
+            Inspector::new(&value, TableStyle::default(), None, ()).unwrap();
+        let visible = |inspector: &Inspector<(), (), ()>| {
+            let table = inspector.pane.table();
+            (0..table.rows_count())
+                .filter_map(|row| table.value_at(row).cloned())
+                .collect_vec()
+        };
+        inspector.set_filter("B");
+        assert_eq!(visible(&inspector), ["a", "a.b"]);
+
+        inspector.set_filter("");
+        inspector.collapse_all();
+        assert_eq!(visible(&inspector), ["a", "d"]);
+    }
+
+    #[test]
+    fn keeps_field_order_and_shows_none() {
+        let server = Server {
+            name: "web".into(),
+            port: None,
+            tags: vec![Some("a"), None],
+            id: u64::MAX,
+        };
+        assert_eq!(
+            properties(&server),
+            pairs(&[
+                ("name", "web"),
+                ("port", "null"),
+                ("tags", "[2]"),
+                ("tags[0]", "a"),
+                ("tags[1]", "null"),
+                ("id", "18446744073709551615"),
+            ])
+        );
+    }
+
+    #[test]
+    fn accepts_values_toml_rejects() {
+        let map = HashMap::from([(7u32, "seven")]);
+        assert_eq!(properties(&map), pairs(&[("7", "seven")]));
+        assert_eq!(properties(&()), pairs(&[("", "null")]));
+        assert_eq!(properties(&None::<u8>), pairs(&[("", "null")]));
+    }
+
+    #[test]
+    fn quotes_keys_that_are_not_identifiers() {
+        let map = BTreeMap::from([("a b", 1), ("c", 2)]);
+        assert_eq!(properties(&map), pairs(&[("\"a b\"", "1"), ("c", "2")]));
+    }
+
+    #[test]
+    fn rejects_maps_keyed_by_containers() {
+        let map = BTreeMap::from([(vec![1], 1)]);
+        let inspector = Inspector::<(), (), ()>::new(&map, TableStyle::default(), None, ());
+        assert!(inspector.is_err());
+    }
+}
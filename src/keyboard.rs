@@ -0,0 +1,81 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use ratatui::crossterm::event::{
+    KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, KeyboardEnhancementFlags,
+    PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+};
+use ratatui::crossterm::{execute, terminal};
+
+static ENHANCED: AtomicBool = AtomicBool::new(false);
+
+// one form per key whatever the terminal sent: shifted letters in uppercase
+// with SHIFT, shifted symbols without it, backtab for shift+tab, repeats as
+// presses and no lock state
+pub fn normalize(ev: &KeyEvent) -> KeyEvent {
+    let mut modifiers = ev.modifiers;
+    let code = match ev.code {
+        KeyCode::Char(c)
+            if c.is_alphabetic()
+                && (c.is_uppercase() || modifiers.contains(KeyModifiers::SHIFT)) =>
+        {
+            modifiers |= KeyModifiers::SHIFT;
+            KeyCode::Char(c.to_uppercase().next().unwrap_or(c))
+        }
+        KeyCode::Char(c) if c != ' ' && !c.is_alphabetic() => {
+            modifiers.remove(KeyModifiers::SHIFT);
+            KeyCode::Char(c)
+        }
+        KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => {
+            modifiers.remove(KeyModifiers::SHIFT);
+            KeyCode::BackTab
+        }
+        KeyCode::BackTab => {
+            modifiers.remove(KeyModifiers::SHIFT);
+            KeyCode::BackTab
+        }
+        code => code,
+    };
+    let kind = match ev.kind {
+        KeyEventKind::Repeat => KeyEventKind::Press,
+        kind => kind,
+    };
+    KeyEvent {
+        code,
+        modifiers,
+        kind,
+        state: KeyEventState::NONE,
+    }
+}
+
+pub fn is_release(ev: &KeyEvent) -> bool {
+    ev.kind == KeyEventKind::Release
+}
+
+// shift+space, ctrl+i vs tab and key releases can only be told apart with the
+// kitty keyboard protocol, returns whether the terminal supports it
+pub fn enable_enhancement() -> std::io::Result<bool> {
+    if !terminal::supports_keyboard_enhancement()? {
+        return Ok(false);
+    }
+    execute!(
+        std::io::stdout(),
+        PushKeyboardEnhancementFlags(
+            KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+                | KeyboardEnhancementFlags::REPORT_EVENT_TYPES
+                | KeyboardEnhancementFlags::REPORT_ALTERNATE_KEYS
+        )
+    )?;
+    ENHANCED.store(true, Ordering::Relaxed);
+    Ok(true)
+}
+
+pub fn disable_enhancement() -> std::io::Result<()> {
+    if ENHANCED.swap(false, Ordering::Relaxed) {
+        execute!(std::io::stdout(), PopKeyboardEnhancementFlags)?;
+    }
+    Ok(())
+}
+
+pub fn is_enhanced() -> bool {
+    ENHANCED.load(Ordering::Relaxed)
+}
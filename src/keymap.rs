@@ -1,11 +1,20 @@
 use std::cmp::Ordering;
+use std::collections::{BTreeMap, VecDeque};
 use std::fmt::Display;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
 
 use itertools::Itertools;
-use ratatui::crossterm::event::KeyEvent;
+use ratatui::crossterm::event::{
+    KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
 use ratatui::layout::{Alignment, Constraint};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+use crate::keyboard;
 use crate::stateful_table::Tabular;
+use crate::which_key::KeyHints;
 
 pub trait KeyMap {
     type Command: Display + Clone;
@@ -13,29 +22,730 @@ pub trait KeyMap {
     fn default() -> Self;
     fn get_shortcuts(&self) -> &[ShortCut<Self::Command>];
     fn get_command(&self, ev: &KeyEvent) -> Option<&Self::Command> {
+        self.get_sequence(&[*ev])
+    }
+    // keys are compared in their normalized form, releases never match
+    fn get_sequence(&self, keys: &[KeyEvent]) -> Option<&Self::Command> {
+        if keys.iter().any(keyboard::is_release) {
+            return None;
+        }
+        let keys = normalize_keys(keys);
         self.get_shortcuts()
             .iter()
-            .find(|s| s.1.contains(ev))
+            .find(|s| {
+                s.1.iter()
+                    .filter_map(Binding::keys)
+                    .any(|seq| normalize_keys(seq) == keys)
+            })
             .map(|s| &s.0)
     }
+    fn is_prefix(&self, keys: &[KeyEvent]) -> bool {
+        if keys.iter().any(keyboard::is_release) {
+            return false;
+        }
+        let keys = normalize_keys(keys);
+        self.get_shortcuts()
+            .iter()
+            .flat_map(|s| &s.1)
+            .filter_map(Binding::keys)
+            .any(|seq| seq.len() > keys.len() && normalize_keys(seq).starts_with(&keys))
+    }
+    // the keys left to type after `keys`, with the command they run
+    fn continuations(&self, keys: &[KeyEvent]) -> Vec<(KeySequence, &Self::Command)> {
+        let keys = normalize_keys(keys);
+        self.get_shortcuts()
+            .iter()
+            .flat_map(|s| {
+                s.1.iter()
+                    .filter_map(Binding::keys)
+                    .map(move |seq| (seq, &s.0))
+            })
+            .filter(|(seq, _)| seq.len() > keys.len() && normalize_keys(seq).starts_with(&keys))
+            .map(|(seq, cmd)| (KeySequence(seq[keys.len()..].to_vec()), cmd))
+            .collect()
+    }
+    // unbound modifiers and click counts fall back to the plain binding
+    fn get_mouse_command(&self, ev: &MouseEvent, clicks: u8) -> Option<&Self::Command> {
+        let action = MouseAction::from_kind(ev.kind)?;
+        let find = |modifiers, clicks| {
+            let binding = MouseBinding {
+                action,
+                modifiers,
+                clicks,
+            };
+            self.get_shortcuts()
+                .iter()
+                .find(|s| s.1.contains(&Binding::Mouse(binding)))
+                .map(|s| &s.0)
+        };
+        find(ev.modifiers, clicks)
+            .or_else(|| find(ev.modifiers, 1))
+            .or_else(|| find(KeyModifiers::NONE, clicks))
+            .or_else(|| find(KeyModifiers::NONE, 1))
+    }
+    // `enhanced` is what `keyboard::enable_enhancement` returned
+    fn validate(&self, enhanced: bool) -> Vec<KeyIssue> {
+        validate_shortcuts(self.get_shortcuts(), enhanced)
+    }
 }
 
-#[derive(Clone)]
-pub struct ShortCut<T: Display + Clone>(pub T, pub Vec<KeyEvent>);
+fn normalize_keys(keys: &[KeyEvent]) -> Vec<KeyEvent> {
+    keys.iter().map(keyboard::normalize).collect()
+}
+
+const MODIFIERS: [(KeyModifiers, &str); 6] = [
+    (KeyModifiers::CONTROL, "ctrl"),
+    (KeyModifiers::ALT, "alt"),
+    (KeyModifiers::SHIFT, "shift"),
+    (KeyModifiers::SUPER, "super"),
+    (KeyModifiers::HYPER, "hyper"),
+    (KeyModifiers::META, "meta"),
+];
+
+pub fn key_to_string(ev: &KeyEvent) -> String {
+    // shifted letters are written in uppercase, as they are parsed back
+    let shifted = matches!(ev.code, KeyCode::Char(c) if c.is_uppercase());
+    let mut mods = MODIFIERS
+        .iter()
+        .filter(|(m, _)| ev.modifiers.contains(*m))
+        .filter(|(m, _)| !(shifted && *m == KeyModifiers::SHIFT))
+        .map(|(_, name)| name.to_string())
+        .collect_vec();
+    mods.push(match ev.code {
+        KeyCode::Char(' ') => "space".into(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(n) => format!("f{n}"),
+        KeyCode::Backspace => "backspace".into(),
+        KeyCode::Enter => "enter".into(),
+        KeyCode::Left => "left".into(),
+        KeyCode::Right => "right".into(),
+        KeyCode::Up => "up".into(),
+        KeyCode::Down => "down".into(),
+        KeyCode::Home => "home".into(),
+        KeyCode::End => "end".into(),
+        KeyCode::PageUp => "pageup".into(),
+        KeyCode::PageDown => "pagedown".into(),
+        KeyCode::Tab => "tab".into(),
+        KeyCode::BackTab => "backtab".into(),
+        KeyCode::Delete => "delete".into(),
+        KeyCode::Insert => "insert".into(),
+        KeyCode::Esc => "esc".into(),
+        code => code.to_string().to_lowercase().replace(' ', ""),
+    });
+    mods.join("+")
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct KeyParseError {
+    pub input: String,
+    pub msg: String,
+}
+impl KeyParseError {
+    fn new(input: &str, msg: impl Into<String>) -> Self {
+        Self {
+            input: input.to_string(),
+            msg: msg.into(),
+        }
+    }
+}
+impl Display for KeyParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} in '{}'", self.msg, self.input)
+    }
+}
+impl std::error::Error for KeyParseError {}
+
+// accepts "ctrl+shift+k", "alt+enter", "f5", "space", "<C-x>" and the names
+// crossterm displays, like "Control+x" or "Page Down"
+pub fn parse_key(input: &str) -> Result<KeyEvent, KeyParseError> {
+    let trimmed = input.trim();
+    if let Some(inner) = trimmed
+        .strip_prefix('<')
+        .and_then(|s| s.strip_suffix('>'))
+        .filter(|s| !s.is_empty())
+    {
+        return parse_vim_key(inner).map_err(|msg| KeyParseError::new(input, msg));
+    }
+    let (mods, key) = match trimmed {
+        "+" => ("", "+"),
+        s if s.ends_with("++") => (&s[..s.len() - 2], "+"),
+        s => s.rsplit_once('+').unwrap_or(("", s)),
+    };
+    let mut modifiers = KeyModifiers::NONE;
+    for name in mods.split('+').filter(|m| !m.is_empty()) {
+        modifiers |= parse_modifier(name)
+            .ok_or_else(|| KeyParseError::new(input, format!("unknown modifier '{name}'")))?;
+    }
+    let code =
+        parse_code(key).ok_or_else(|| KeyParseError::new(input, format!("unknown key '{key}'")))?;
+    Ok(new_key(code, modifiers))
+}
+
+// accepts whitespace separated keys ("ctrl+x ctrl+s", "g g") and vim notation
+// ("<C-x><C-s>", "<space>fs", "gg")
+pub fn parse_keys(input: &str) -> Result<KeySequence, KeyParseError> {
+    if let Ok(key) = parse_key(input) {
+        return Ok(KeySequence(vec![key]));
+    }
+    let mut keys = vec![];
+    for token in input.split_whitespace() {
+        match parse_key(token) {
+            Ok(key) => keys.push(key),
+            Err(err) if token.contains('+') => return Err(err),
+            Err(err) => keys.extend(parse_vim_keys(token).map_err(|_| err)?),
+        }
+    }
+    if keys.is_empty() {
+        return Err(KeyParseError::new(input, "empty key sequence"));
+    }
+    Ok(KeySequence(keys))
+}
+
+fn parse_vim_keys(input: &str) -> Result<Vec<KeyEvent>, KeyParseError> {
+    let mut keys = vec![];
+    let mut rest = input;
+    while let Some(c) = rest.chars().next() {
+        match rest.find('>').filter(|_| c == '<' && rest.len() > 1) {
+            Some(end) => {
+                let key = parse_vim_key(&rest[1..end]).map_err(|m| KeyParseError::new(input, m))?;
+                keys.push(key);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                keys.push(new_key(KeyCode::Char(c), KeyModifiers::NONE));
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    Ok(keys)
+}
+
+fn parse_vim_key(inner: &str) -> Result<KeyEvent, String> {
+    let (mods, key) = match inner {
+        s if s.ends_with("--") => (&s[..s.len() - 2], "-"),
+        s => s.rsplit_once('-').unwrap_or(("", s)),
+    };
+    let mut modifiers = KeyModifiers::NONE;
+    for name in mods.split('-').filter(|m| !m.is_empty()) {
+        modifiers |= match name.to_ascii_lowercase().as_str() {
+            "c" => KeyModifiers::CONTROL,
+            "a" | "m" => KeyModifiers::ALT,
+            "s" => KeyModifiers::SHIFT,
+            "d" => KeyModifiers::SUPER,
+            name => parse_modifier(name).ok_or(format!("unknown modifier '{name}'"))?,
+        };
+    }
+    let code = match key.to_ascii_lowercase().as_str() {
+        "cr" | "return" => KeyCode::Enter,
+        "bs" => KeyCode::Backspace,
+        "lt" => KeyCode::Char('<'),
+        "gt" => KeyCode::Char('>'),
+        "bar" => KeyCode::Char('|'),
+        _ => parse_code(key).ok_or(format!("unknown key '{key}'"))?,
+    };
+    Ok(new_key(code, modifiers))
+}
+
+fn parse_modifier(name: &str) -> Option<KeyModifiers> {
+    Some(match name.to_ascii_lowercase().as_str() {
+        "ctrl" | "control" => KeyModifiers::CONTROL,
+        "alt" | "option" => KeyModifiers::ALT,
+        "shift" => KeyModifiers::SHIFT,
+        "super" | "cmd" | "command" | "win" => KeyModifiers::SUPER,
+        "hyper" => KeyModifiers::HYPER,
+        "meta" => KeyModifiers::META,
+        _ => return None,
+    })
+}
+
+fn parse_code(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+    let name = name.to_ascii_lowercase().replace([' ', '_', '-'], "");
+    Some(match name.as_str() {
+        "space" => KeyCode::Char(' '),
+        "backspace" => KeyCode::Backspace,
+        "enter" | "return" => KeyCode::Enter,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" | "pgup" => KeyCode::PageUp,
+        "pagedown" | "pgdn" => KeyCode::PageDown,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" | "ins" => KeyCode::Insert,
+        "esc" | "escape" => KeyCode::Esc,
+        "null" => KeyCode::Null,
+        "capslock" => KeyCode::CapsLock,
+        "scrolllock" => KeyCode::ScrollLock,
+        "numlock" => KeyCode::NumLock,
+        "printscreen" => KeyCode::PrintScreen,
+        "pause" => KeyCode::Pause,
+        "menu" => KeyCode::Menu,
+        "begin" => KeyCode::KeypadBegin,
+        f => KeyCode::F(f.strip_prefix('f')?.parse().ok()?),
+    })
+}
+
+// crossterm reports shifted letters in uppercase along with SHIFT
+fn new_key(code: KeyCode, mut modifiers: KeyModifiers) -> KeyEvent {
+    let code = match code {
+        KeyCode::Char(c) if c.is_alphabetic() && modifiers.contains(KeyModifiers::SHIFT) => {
+            KeyCode::Char(c.to_uppercase().next().unwrap_or(c))
+        }
+        KeyCode::Char(c) if c.is_uppercase() => {
+            modifiers |= KeyModifiers::SHIFT;
+            KeyCode::Char(c)
+        }
+        code => code,
+    };
+    KeyEvent::new(code, modifiers)
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct KeySequence(pub Vec<KeyEvent>);
+impl From<KeyEvent> for KeySequence {
+    fn from(ev: KeyEvent) -> Self {
+        Self(vec![ev])
+    }
+}
+impl From<Vec<KeyEvent>> for KeySequence {
+    fn from(keys: Vec<KeyEvent>) -> Self {
+        Self(keys)
+    }
+}
+impl Display for KeySequence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.iter().map(key_to_string).join(" "))
+    }
+}
+impl FromStr for KeySequence {
+    type Err = KeyParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_keys(s)
+    }
+}
+impl TryFrom<String> for KeySequence {
+    type Error = KeyParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        parse_keys(&value)
+    }
+}
+impl From<KeySequence> for String {
+    fn from(value: KeySequence) -> Self {
+        value.to_string()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum MouseAction {
+    Click(MouseButton),
+    ScrollUp,
+    ScrollDown,
+    ScrollLeft,
+    ScrollRight,
+}
+impl MouseAction {
+    pub fn from_kind(kind: MouseEventKind) -> Option<Self> {
+        Some(match kind {
+            MouseEventKind::Down(button) => MouseAction::Click(button),
+            MouseEventKind::ScrollUp => MouseAction::ScrollUp,
+            MouseEventKind::ScrollDown => MouseAction::ScrollDown,
+            MouseEventKind::ScrollLeft => MouseAction::ScrollLeft,
+            MouseEventKind::ScrollRight => MouseAction::ScrollRight,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct MouseBinding {
+    pub action: MouseAction,
+    pub modifiers: KeyModifiers,
+    pub clicks: u8,
+}
+impl MouseBinding {
+    pub fn new(action: MouseAction, modifiers: KeyModifiers) -> Self {
+        Self {
+            action,
+            modifiers,
+            clicks: 1,
+        }
+    }
+    pub fn with_clicks(mut self, clicks: u8) -> Self {
+        self.clicks = clicks.clamp(1, MAX_CLICKS);
+        self
+    }
+}
+impl Display for MouseBinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (m, name) in MODIFIERS {
+            if self.modifiers.contains(m) {
+                write!(f, "{name}+")?;
+            }
+        }
+        let clicks = match self.clicks {
+            2 => "double",
+            3 => "triple",
+            _ => "",
+        };
+        match self.action {
+            MouseAction::Click(MouseButton::Left) => write!(f, "left{clicks}click"),
+            MouseAction::Click(MouseButton::Right) => write!(f, "right{clicks}click"),
+            MouseAction::Click(MouseButton::Middle) => write!(f, "middle{clicks}click"),
+            MouseAction::ScrollUp => write!(f, "scrollup"),
+            MouseAction::ScrollDown => write!(f, "scrolldown"),
+            MouseAction::ScrollLeft => write!(f, "scrollleft"),
+            MouseAction::ScrollRight => write!(f, "scrollright"),
+        }
+    }
+}
+
+const MAX_CLICKS: u8 = 3;
+
+// accepts "leftclick", "ctrl+middleclick", "leftdoubleclick", "alt+scrollup";
+// `None` when the input does not name a mouse action
+pub fn parse_mouse(input: &str) -> Result<Option<MouseBinding>, KeyParseError> {
+    let trimmed = input.trim();
+    let (mods, name) = trimmed.rsplit_once('+').unwrap_or(("", trimmed));
+    let name = name.to_ascii_lowercase().replace([' ', '_', '-'], "");
+    let click = |button, rest: &str| {
+        let clicks = match rest {
+            "click" => 1,
+            "doubleclick" => 2,
+            "tripleclick" => 3,
+            _ => return None,
+        };
+        Some(MouseBinding::new(MouseAction::Click(button), KeyModifiers::NONE).with_clicks(clicks))
+    };
+    let binding = match name.as_str() {
+        "scrollup" => MouseBinding::new(MouseAction::ScrollUp, KeyModifiers::NONE),
+        "scrolldown" => MouseBinding::new(MouseAction::ScrollDown, KeyModifiers::NONE),
+        "scrollleft" => MouseBinding::new(MouseAction::ScrollLeft, KeyModifiers::NONE),
+        "scrollright" => MouseBinding::new(MouseAction::ScrollRight, KeyModifiers::NONE),
+        "click" | "doubleclick" | "tripleclick" => click(MouseButton::Left, &name).unwrap(),
+        name => {
+            let parsed = [
+                ("left", MouseButton::Left),
+                ("right", MouseButton::Right),
+                ("middle", MouseButton::Middle),
+            ]
+            .into_iter()
+            .find_map(|(prefix, button)| click(button, name.strip_prefix(prefix)?));
+            match parsed {
+                Some(binding) => binding,
+                None => return Ok(None),
+            }
+        }
+    };
+    let mut modifiers = KeyModifiers::NONE;
+    for name in mods.split('+').filter(|m| !m.is_empty()) {
+        modifiers |= parse_modifier(name)
+            .ok_or_else(|| KeyParseError::new(input, format!("unknown modifier '{name}'")))?;
+    }
+    Ok(Some(MouseBinding {
+        modifiers,
+        ..binding
+    }))
+}
+
+// what a `ShortCut` is bound to: a key sequence or a mouse action
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Binding {
+    Keys(KeySequence),
+    Mouse(MouseBinding),
+}
+impl Binding {
+    pub fn keys(&self) -> Option<&[KeyEvent]> {
+        match self {
+            Binding::Keys(seq) => Some(&seq.0),
+            Binding::Mouse(_) => None,
+        }
+    }
+    pub fn normalized(&self) -> Self {
+        match self {
+            Binding::Keys(seq) => Binding::Keys(KeySequence(normalize_keys(&seq.0))),
+            mouse => mouse.clone(),
+        }
+    }
+}
+impl From<KeyEvent> for Binding {
+    fn from(ev: KeyEvent) -> Self {
+        Binding::Keys(ev.into())
+    }
+}
+impl From<KeySequence> for Binding {
+    fn from(seq: KeySequence) -> Self {
+        Binding::Keys(seq)
+    }
+}
+impl From<MouseBinding> for Binding {
+    fn from(mouse: MouseBinding) -> Self {
+        Binding::Mouse(mouse)
+    }
+}
+impl Display for Binding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Binding::Keys(seq) => write!(f, "{seq}"),
+            Binding::Mouse(mouse) => write!(f, "{mouse}"),
+        }
+    }
+}
+impl FromStr for Binding {
+    type Err = KeyParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_binding(s)
+    }
+}
+impl TryFrom<String> for Binding {
+    type Error = KeyParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        parse_binding(&value)
+    }
+}
+impl From<Binding> for String {
+    fn from(value: Binding) -> Self {
+        value.to_string()
+    }
+}
+
+pub fn parse_binding(input: &str) -> Result<Binding, KeyParseError> {
+    match parse_mouse(input)? {
+        Some(mouse) => Ok(Binding::Mouse(mouse)),
+        None => parse_keys(input).map(Binding::Keys),
+    }
+}
+
+// terminals report single presses, repeated presses of the same button at
+// the same spot are counted here
+#[derive(Clone, Debug)]
+pub struct ClickCounter {
+    last: Option<(MouseButton, u16, u16, Instant)>,
+    clicks: u8,
+    interval: Duration,
+}
+impl Default for ClickCounter {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(500))
+    }
+}
+impl ClickCounter {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            last: None,
+            clicks: 0,
+            interval,
+        }
+    }
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+    pub fn register(&mut self, ev: &MouseEvent) -> u8 {
+        let MouseEventKind::Down(button) = ev.kind else {
+            return 1;
+        };
+        let repeated = self.last.is_some_and(|(b, col, row, at)| {
+            b == button && col == ev.column && row == ev.row && at.elapsed() <= self.interval
+        });
+        self.clicks = if repeated {
+            self.clicks % MAX_CLICKS + 1
+        } else {
+            1
+        };
+        self.last = Some((button, ev.column, ev.row, Instant::now()));
+        self.clicks
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Resolution<C> {
+    Pending,
+    // the count typed before the keys, as in `5j`
+    Matched(C, Option<usize>),
+    NoMatch(Vec<KeyEvent>),
+}
+
+// when a sequence is both a match and a prefix of a longer one, the resolver
+// waits for the longer one and falls back to the match once the timeout expires
+#[derive(Clone, Debug)]
+pub struct KeyResolver {
+    pending: Vec<KeyEvent>,
+    // keys typed after a match that was waiting for a longer sequence
+    queued: VecDeque<KeyEvent>,
+    count: Option<usize>,
+    since: Instant,
+    timeout: Duration,
+    hint_delay: Option<Duration>,
+}
+impl Default for KeyResolver {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(1000))
+    }
+}
+impl KeyResolver {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            pending: vec![],
+            queued: VecDeque::new(),
+            count: None,
+            since: Instant::now(),
+            timeout,
+            hint_delay: None,
+        }
+    }
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+    // with hints, a pending prefix that is not a match itself waits for the next key
+    pub fn set_hint_delay(&mut self, delay: Option<Duration>) {
+        self.hint_delay = delay;
+    }
+    pub fn hints<K: KeyMap>(&self, keymap: &K) -> Option<KeyHints> {
+        let delay = self.hint_delay?;
+        if self.pending.is_empty() || self.since.elapsed() < delay {
+            return None;
+        }
+        Some(KeyHints::new(keymap, &self.pending))
+    }
+    pub fn pending(&self) -> &[KeyEvent] {
+        &self.pending
+    }
+    pub fn count(&self) -> Option<usize> {
+        self.count
+    }
+    pub fn pending_string(&self) -> String {
+        let keys = KeySequence(self.pending.clone()).to_string();
+        match self.count {
+            Some(count) => format!("{count}{keys}"),
+            None => keys,
+        }
+    }
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.queued.clear();
+        self.count = None;
+    }
+    pub fn resolve<K: KeyMap>(&mut self, keymap: &K, ev: &KeyEvent) -> Resolution<K::Command> {
+        self.queued.push_back(*ev);
+        self.drain(keymap).unwrap_or(Resolution::Pending)
+    }
+    // resolves the keys queued behind a match first, then falls back to the
+    // pending match once the timeout expires
+    pub fn tick<K: KeyMap>(&mut self, keymap: &K) -> Option<Resolution<K::Command>> {
+        if let Some(resolution) = self.drain(keymap) {
+            return Some(resolution);
+        }
+        if self.pending.is_empty() || self.since.elapsed() < self.timeout {
+            return None;
+        }
+        if self.hint_delay.is_some() && keymap.get_sequence(&self.pending).is_none() {
+            return None;
+        }
+        let keys = std::mem::take(&mut self.pending);
+        let count = self.count.take();
+        Some(match keymap.get_sequence(&keys) {
+            Some(cmd) => Resolution::Matched(cmd.clone(), count),
+            None => Resolution::NoMatch(keys),
+        })
+    }
+
+    // --- helpers
+    fn drain<K: KeyMap>(&mut self, keymap: &K) -> Option<Resolution<K::Command>> {
+        while let Some(ev) = self.queued.pop_front() {
+            match self.resolve_key(keymap, &ev) {
+                Resolution::Pending => continue,
+                resolution => return Some(resolution),
+            }
+        }
+        None
+    }
+    fn resolve_key<K: KeyMap>(&mut self, keymap: &K, ev: &KeyEvent) -> Resolution<K::Command> {
+        // reported with the keyboard enhancement only, the press was resolved already
+        if keyboard::is_release(ev) {
+            return Resolution::NoMatch(vec![]);
+        }
+        let mut keys = self.pending.clone();
+        keys.push(*ev);
+        let cancel = ev.code == KeyCode::Esc && (!self.pending.is_empty() || self.count.is_some());
+        if cancel && keymap.get_sequence(&keys).is_none() {
+            self.clear();
+            return Resolution::NoMatch(keys);
+        }
+        // unbound digits build up a count, a leading `0` is left to the keymap
+        if let Some(digit) = self.count_digit(keymap, ev) {
+            self.count = Some(
+                self.count
+                    .unwrap_or(0)
+                    .saturating_mul(10)
+                    .saturating_add(digit),
+            );
+            return Resolution::Pending;
+        }
+        if keymap.is_prefix(&keys) {
+            self.pending = keys;
+            self.since = Instant::now();
+            return Resolution::Pending;
+        }
+        if let Some(cmd) = keymap.get_sequence(&keys) {
+            self.pending.clear();
+            return Resolution::Matched(cmd.clone(), self.count.take());
+        }
+        match keymap.get_sequence(&self.pending).cloned() {
+            // the pending keys were a match waiting for a longer one: run it
+            // and resolve the new key on its own next
+            Some(cmd) => {
+                self.pending.clear();
+                self.queued.push_front(*ev);
+                Resolution::Matched(cmd, self.count.take())
+            }
+            None => {
+                self.clear();
+                Resolution::NoMatch(keys)
+            }
+        }
+    }
+    fn clear(&mut self) {
+        self.pending.clear();
+        self.count = None;
+    }
+    fn count_digit<K: KeyMap>(&self, keymap: &K, ev: &KeyEvent) -> Option<usize> {
+        if !self.pending.is_empty() || !ev.modifiers.difference(KeyModifiers::SHIFT).is_empty() {
+            return None;
+        }
+        let digit = match ev.code {
+            KeyCode::Char(c) => c.to_digit(10)?,
+            _ => return None,
+        };
+        if digit == 0 && self.count.is_none() {
+            return None;
+        }
+        if keymap.get_command(ev).is_some() || keymap.is_prefix(&[*ev]) {
+            return None;
+        }
+        Some(digit as usize)
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ShortCut<T: Display + Clone>(pub T, pub Vec<Binding>);
 impl<T: Display + Clone> Tabular for ShortCut<T> {
     type Value = ();
     fn value(&self) -> Self::Value {}
     fn content(&self) -> Vec<String> {
-        let keyevent_to_string = |ev: &KeyEvent| {
-            let mut mods = ev.modifiers.iter().map(|m| m.to_string()).collect_vec();
-            mods.push(ev.code.to_string());
-            mods.join("+")
-        };
-
-        vec![
-            format!("{}", self.0),
-            self.1.iter().map(keyevent_to_string).join(", "),
-        ]
+        vec![format!("{}", self.0), self.1.iter().join(", ")]
     }
     fn column_constraints() -> Vec<fn(u16) -> Constraint> {
         vec![Constraint::Length, Constraint::Fill]
@@ -51,3 +761,824 @@ impl<T: Display + Clone> Tabular for ShortCut<T> {
         Ordering::Equal
     }
 }
+
+// a `[keys]` table: `go_down = ["j", "down"]`, or a single `go_down = "j"`
+#[derive(Clone)]
+pub struct KeyBindings<T: Display + Clone>(pub Vec<ShortCut<T>>);
+impl<T: Display + Clone> Default for KeyBindings<T> {
+    fn default() -> Self {
+        Self(vec![])
+    }
+}
+impl<'de, T> Deserialize<'de> for KeyBindings<T>
+where
+    T: Display + Clone + DeserializeOwned,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Keys {
+            One(String),
+            Many(Vec<String>),
+        }
+
+        let table = BTreeMap::<String, Keys>::deserialize(deserializer)?;
+        table
+            .into_iter()
+            .map(|(name, keys)| {
+                let cmd =
+                    T::deserialize(serde::de::value::StrDeserializer::<D::Error>::new(&name))?;
+                let keys = match keys {
+                    Keys::One(key) => vec![key],
+                    Keys::Many(keys) => keys,
+                };
+                let keys = keys
+                    .iter()
+                    .map(|k| parse_binding(k).map_err(serde::de::Error::custom))
+                    .collect::<Result<_, _>>()?;
+                Ok(ShortCut(cmd, keys))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+impl<T: Display + Clone + Serialize> Serialize for KeyBindings<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for ShortCut(cmd, keys) in &self.0 {
+            map.serialize_entry(cmd, keys)?;
+        }
+        map.end()
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct KeyConflict {
+    pub layer: String,
+    pub keys: Binding,
+    pub command: String,
+    pub previous: String,
+}
+impl Display for KeyConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: '{}' is bound to '{}' instead of '{}'",
+            self.layer, self.keys, self.command, self.previous
+        )
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum KeyIssue {
+    // several commands share the binding, only the first one ever runs
+    Duplicate {
+        binding: Binding,
+        commands: Vec<String>,
+    },
+    // the binding only runs once the resolver timeout expires
+    Shadowed {
+        binding: Binding,
+        command: String,
+        by: Binding,
+        by_command: String,
+    },
+    Unsendable {
+        binding: Binding,
+        command: String,
+        reason: String,
+    },
+}
+impl KeyIssue {
+    pub fn binding(&self) -> &Binding {
+        match self {
+            KeyIssue::Duplicate { binding, .. }
+            | KeyIssue::Shadowed { binding, .. }
+            | KeyIssue::Unsendable { binding, .. } => binding,
+        }
+    }
+}
+impl Display for KeyIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyIssue::Duplicate { binding, commands } => write!(
+                f,
+                "'{binding}' is bound to {}",
+                commands.iter().map(|c| format!("'{c}'")).join(", ")
+            ),
+            KeyIssue::Shadowed {
+                binding,
+                command,
+                by,
+                by_command,
+            } => write!(
+                f,
+                "'{binding}' ({command}) waits for the timeout because of '{by}' ({by_command})"
+            ),
+            KeyIssue::Unsendable {
+                binding,
+                command,
+                reason,
+            } => write!(f, "'{binding}' ({command}) cannot be sent: {reason}"),
+        }
+    }
+}
+impl Tabular for KeyIssue {
+    type Value = ();
+    fn value(&self) -> Self::Value {}
+    fn content(&self) -> Vec<String> {
+        let (kind, commands) = match self {
+            KeyIssue::Duplicate { commands, .. } => ("duplicate", commands.join(", ")),
+            KeyIssue::Shadowed { command, .. } => ("shadowed", command.clone()),
+            KeyIssue::Unsendable { command, .. } => ("unsendable", command.clone()),
+        };
+        vec![
+            self.binding().to_string(),
+            kind.to_string(),
+            commands,
+            self.to_string(),
+        ]
+    }
+    fn column_constraints() -> Vec<fn(u16) -> Constraint> {
+        vec![
+            Constraint::Length,
+            Constraint::Length,
+            Constraint::Length,
+            Constraint::Fill,
+        ]
+    }
+    fn column_names() -> Option<Vec<String>> {
+        Some(vec![
+            "Key".to_string(),
+            "Issue".to_string(),
+            "Command".to_string(),
+            "Details".to_string(),
+        ])
+    }
+    fn column_alignments() -> Option<Vec<Alignment>> {
+        Some(vec![Alignment::Left; 4])
+    }
+}
+
+pub fn validate_shortcuts<C: Display + Clone>(
+    shortcuts: &[ShortCut<C>],
+    enhanced: bool,
+) -> Vec<KeyIssue> {
+    let bindings = shortcuts
+        .iter()
+        .flat_map(|s| s.1.iter().unique().map(move |b| (b, s.0.to_string())))
+        .collect_vec();
+    let normalized = bindings.iter().map(|(b, _)| b.normalized()).collect_vec();
+    let mut issues = vec![];
+    let mut seen = vec![];
+    for (idx, (binding, _)) in bindings.iter().enumerate() {
+        let key = &normalized[idx];
+        if seen.contains(&key) {
+            continue;
+        }
+        seen.push(key);
+        let commands = bindings
+            .iter()
+            .zip(&normalized)
+            .filter(|(_, other)| *other == key)
+            .map(|((_, c), _)| c.clone())
+            .unique()
+            .collect_vec();
+        if commands.len() > 1 {
+            issues.push(KeyIssue::Duplicate {
+                binding: (*binding).clone(),
+                commands,
+            });
+        }
+    }
+    for (idx, (binding, command)) in bindings.iter().enumerate() {
+        let Some(keys) = normalized[idx].keys() else {
+            continue;
+        };
+        let longer = bindings.iter().zip(&normalized).find(|(_, other)| {
+            other
+                .keys()
+                .is_some_and(|other| other.len() > keys.len() && other.starts_with(keys))
+        });
+        if let Some(((by, by_command), _)) = longer {
+            issues.push(KeyIssue::Shadowed {
+                binding: (*binding).clone(),
+                command: command.clone(),
+                by: (*by).clone(),
+                by_command: by_command.clone(),
+            });
+        }
+        let reason = binding
+            .keys()
+            .into_iter()
+            .flatten()
+            .find_map(|ev| unsendable(ev, enhanced));
+        if let Some(reason) = reason {
+            issues.push(KeyIssue::Unsendable {
+                binding: (*binding).clone(),
+                command: command.clone(),
+                reason: reason.to_string(),
+            });
+        }
+    }
+    issues
+}
+
+// what legacy terminal encodings cannot tell apart from another key, most of
+// it is sent as is with the keyboard enhancement
+fn unsendable(ev: &KeyEvent, enhanced: bool) -> Option<&'static str> {
+    let mods = ev.modifiers;
+    let ctrl = mods.contains(KeyModifiers::CONTROL);
+    let shift = mods.contains(KeyModifiers::SHIFT);
+    match ev.code {
+        KeyCode::Null => return Some("null is not a key"),
+        KeyCode::Char(c) if shift && c != ' ' && !c.is_alphabetic() => {
+            return Some("shifted symbols are sent as the symbol itself")
+        }
+        _ if enhanced => return None,
+        _ => {}
+    }
+    if mods.intersects(KeyModifiers::SUPER | KeyModifiers::HYPER | KeyModifiers::META) {
+        return Some("super, hyper and meta need the kitty keyboard protocol");
+    }
+    Some(match ev.code {
+        KeyCode::Char(' ') if shift => "shift+space is sent as space",
+        KeyCode::Char(c) if shift && ctrl && c.is_alphabetic() => {
+            "ctrl+shift+letter is sent as ctrl+letter"
+        }
+        KeyCode::Char('h') if ctrl => "ctrl+h is sent as backspace",
+        KeyCode::Char('i') if ctrl => "ctrl+i is sent as tab",
+        KeyCode::Char('m') if ctrl => "ctrl+m is sent as enter",
+        KeyCode::Char('[') if ctrl => "ctrl+[ is sent as esc",
+        KeyCode::CapsLock
+        | KeyCode::ScrollLock
+        | KeyCode::NumLock
+        | KeyCode::PrintScreen
+        | KeyCode::Pause
+        | KeyCode::Menu
+        | KeyCode::KeypadBegin
+        | KeyCode::Media(_)
+        | KeyCode::Modifier(_) => "this key needs the kitty keyboard protocol",
+        _ => return None,
+    })
+}
+
+// built-in defaults, then any number of layers (app defaults, user config...):
+// a command listed in a layer gets exactly the keys of that layer, an empty
+// list removes it, and keys taken from another command are reported
+pub struct LayeredKeyMap<K: KeyMap> {
+    shortcuts: Vec<ShortCut<K::Command>>,
+    conflicts: Vec<KeyConflict>,
+}
+impl<K: KeyMap> Clone for LayeredKeyMap<K> {
+    fn clone(&self) -> Self {
+        Self {
+            shortcuts: self.shortcuts.clone(),
+            conflicts: self.conflicts.clone(),
+        }
+    }
+}
+impl<K: KeyMap> LayeredKeyMap<K>
+where
+    K::Command: PartialEq,
+{
+    pub fn with_layer(mut self, name: &str, bindings: KeyBindings<K::Command>) -> Self {
+        self.add_layer(name, bindings);
+        self
+    }
+    pub fn add_layer(&mut self, name: &str, bindings: KeyBindings<K::Command>) {
+        for ShortCut(cmd, keys) in bindings.0 {
+            for key in &keys {
+                // compared in their normalized form, `shift+!` is `!`
+                let normalized = key.normalized();
+                for ShortCut(other, other_keys) in self.shortcuts.iter_mut() {
+                    if *other != cmd && other_keys.iter().any(|k| k.normalized() == normalized) {
+                        self.conflicts.push(KeyConflict {
+                            layer: name.to_string(),
+                            keys: key.clone(),
+                            command: cmd.to_string(),
+                            previous: other.to_string(),
+                        });
+                        other_keys.retain(|k| k.normalized() != normalized);
+                    }
+                }
+            }
+            match self.shortcuts.iter().position(|s| s.0 == cmd) {
+                Some(pos) if keys.is_empty() => {
+                    self.shortcuts.remove(pos);
+                }
+                Some(pos) => self.shortcuts[pos].1 = keys,
+                None if keys.is_empty() => {}
+                None => self.shortcuts.push(ShortCut(cmd, keys)),
+            }
+        }
+        self.shortcuts.retain(|s| !s.1.is_empty());
+    }
+    pub fn conflicts(&self) -> &[KeyConflict] {
+        &self.conflicts
+    }
+}
+impl<K: KeyMap> KeyMap for LayeredKeyMap<K> {
+    type Command = K::Command;
+
+    fn default() -> Self {
+        Self {
+            shortcuts: K::default().get_shortcuts().to_vec(),
+            conflicts: vec![],
+        }
+    }
+    fn get_shortcuts(&self) -> &[ShortCut<Self::Command>] {
+        &self.shortcuts
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum Mode {
+    #[default]
+    Normal,
+    Insert,
+    Visual,
+    Custom(String),
+}
+impl Display for Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mode::Normal => write!(f, "normal"),
+            Mode::Insert => write!(f, "insert"),
+            Mode::Visual => write!(f, "visual"),
+            Mode::Custom(name) => write!(f, "{name}"),
+        }
+    }
+}
+impl From<String> for Mode {
+    fn from(value: String) -> Self {
+        match value.to_lowercase().as_str() {
+            "normal" => Mode::Normal,
+            "insert" => Mode::Insert,
+            "visual" => Mode::Visual,
+            _ => Mode::Custom(value),
+        }
+    }
+}
+impl From<Mode> for String {
+    fn from(value: Mode) -> Self {
+        value.to_string()
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModeCommand {
+    Push(Mode),
+    Pop,
+    Set(Mode),
+}
+impl Display for ModeCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModeCommand::Push(mode) => write!(f, "enter {mode} mode"),
+            ModeCommand::Pop => write!(f, "leave mode"),
+            ModeCommand::Set(mode) => write!(f, "switch to {mode} mode"),
+        }
+    }
+}
+
+// one binding table per mode, `get_shortcuts` answers for the current one
+#[derive(Clone)]
+pub struct ModalKeyMap<C: Display + Clone> {
+    tables: Vec<(Mode, Vec<ShortCut<C>>)>,
+    mode: Mode,
+}
+impl<C: Display + Clone> ModalKeyMap<C> {
+    pub fn with_mode(mut self, mode: Mode, shortcuts: Vec<ShortCut<C>>) -> Self {
+        self.set_shortcuts(mode, shortcuts);
+        self
+    }
+    pub fn set_shortcuts(&mut self, mode: Mode, shortcuts: Vec<ShortCut<C>>) {
+        match self.tables.iter_mut().find(|(m, _)| *m == mode) {
+            Some((_, table)) => *table = shortcuts,
+            None => self.tables.push((mode, shortcuts)),
+        }
+    }
+    pub fn shortcuts_in(&self, mode: &Mode) -> &[ShortCut<C>] {
+        self.tables
+            .iter()
+            .find(|(m, _)| m == mode)
+            .map_or(&[], |(_, table)| table)
+    }
+    pub fn modes(&self) -> Vec<&Mode> {
+        self.tables.iter().map(|(m, _)| m).collect()
+    }
+    pub fn mode(&self) -> &Mode {
+        &self.mode
+    }
+    pub fn set_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+    }
+}
+impl ModalKeyMap<ModeCommand> {
+    pub fn vim() -> Self {
+        let key = |code| Binding::from(KeyEvent::new(code, KeyModifiers::NONE));
+        let leave = || ShortCut(ModeCommand::Pop, vec![key(KeyCode::Esc)]);
+        <Self as KeyMap>::default()
+            .with_mode(
+                Mode::Normal,
+                vec![
+                    ShortCut(
+                        ModeCommand::Push(Mode::Insert),
+                        vec![key(KeyCode::Char('i'))],
+                    ),
+                    ShortCut(
+                        ModeCommand::Push(Mode::Visual),
+                        vec![key(KeyCode::Char('v'))],
+                    ),
+                ],
+            )
+            .with_mode(Mode::Insert, vec![leave()])
+            .with_mode(Mode::Visual, vec![leave()])
+    }
+}
+impl<C: Display + Clone> KeyMap for ModalKeyMap<C> {
+    type Command = C;
+
+    fn default() -> Self {
+        Self {
+            tables: vec![],
+            mode: Mode::Normal,
+        }
+    }
+    fn get_shortcuts(&self) -> &[ShortCut<Self::Command>] {
+        self.shortcuts_in(&self.mode)
+    }
+    fn validate(&self, enhanced: bool) -> Vec<KeyIssue> {
+        self.tables
+            .iter()
+            .flat_map(|(_, table)| validate_shortcuts(table, enhanced))
+            .collect()
+    }
+}
+
+// the bindings a `CommandKeyMap` starts with and a longer explanation shown
+// next to the command name in the help
+pub trait Command: Display + Clone {
+    fn default_bindings() -> Vec<ShortCut<Self>> {
+        vec![]
+    }
+    fn description(&self) -> Option<String> {
+        None
+    }
+}
+impl Command for ModeCommand {}
+
+#[derive(Clone)]
+pub struct CommandKeyMap<C: Command>(pub Vec<ShortCut<C>>);
+impl<C: Command> KeyMap for CommandKeyMap<C> {
+    type Command = C;
+
+    fn default() -> Self {
+        Self(C::default_bindings())
+    }
+    fn get_shortcuts(&self) -> &[ShortCut<Self::Command>] {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestKeyMap(Vec<ShortCut<String>>);
+    impl KeyMap for TestKeyMap {
+        type Command = String;
+
+        fn default() -> Self {
+            let bind = |cmd: &str, keys: &str| {
+                ShortCut(cmd.to_string(), vec![parse_binding(keys).unwrap()])
+            };
+            Self(vec![
+                bind("delete", "d"),
+                bind("delete line", "dd"),
+                bind("top", "gg"),
+                bind("save", "<C-x><C-s>"),
+                bind("down", "j"),
+            ])
+        }
+        fn get_shortcuts(&self) -> &[ShortCut<String>] {
+            &self.0
+        }
+    }
+
+    fn press(resolver: &mut KeyResolver, keys: &str) -> Vec<Resolution<String>> {
+        let keymap = TestKeyMap::default();
+        let mut out = vec![];
+        for key in parse_keys(keys).unwrap().0 {
+            out.push(resolver.resolve(&keymap, &key));
+            out.extend(resolver.tick(&keymap));
+        }
+        out
+    }
+    fn matched(cmd: &str) -> Resolution<String> {
+        Resolution::Matched(cmd.to_string(), None)
+    }
+
+    #[test]
+    fn resolves_sequences() {
+        let mut resolver = KeyResolver::default();
+        assert_eq!(
+            press(&mut resolver, "gg"),
+            [Resolution::Pending, matched("top")]
+        );
+        assert_eq!(
+            press(&mut resolver, "<C-x><C-s>"),
+            [Resolution::Pending, matched("save")]
+        );
+        assert_eq!(
+            press(&mut resolver, "dd"),
+            [Resolution::Pending, matched("delete line")]
+        );
+    }
+
+    #[test]
+    fn keeps_the_key_after_a_pending_match() {
+        let mut resolver = KeyResolver::default();
+        assert_eq!(
+            press(&mut resolver, "dgg"),
+            [Resolution::Pending, matched("delete"), matched("top")]
+        );
+        assert_eq!(
+            press(&mut resolver, "dj"),
+            [Resolution::Pending, matched("delete"), matched("down")]
+        );
+    }
+
+    #[test]
+    fn keeps_queued_keys_without_ticks() {
+        let keymap = TestKeyMap::default();
+        let mut resolver = KeyResolver::default();
+        let keys = parse_keys("dgg").unwrap().0;
+        let out: Vec<_> = keys.iter().map(|k| resolver.resolve(&keymap, k)).collect();
+        assert_eq!(
+            out,
+            [Resolution::Pending, matched("delete"), matched("top")]
+        );
+    }
+
+    #[test]
+    fn falls_back_to_the_match_on_timeout() {
+        let keymap = TestKeyMap::default();
+        let mut resolver = KeyResolver::new(Duration::ZERO);
+        assert_eq!(
+            resolver.resolve(&keymap, &parse_key("d").unwrap()),
+            Resolution::Pending
+        );
+        assert_eq!(resolver.tick(&keymap), Some(matched("delete")));
+        assert_eq!(resolver.tick(&keymap), None);
+
+        let mut resolver = KeyResolver::new(Duration::MAX);
+        assert_eq!(press(&mut resolver, "d"), [Resolution::Pending]);
+        assert_eq!(
+            press(&mut resolver, "j"),
+            [matched("delete"), matched("down")]
+        );
+    }
+
+    #[test]
+    fn counts_and_cancels() {
+        let mut resolver = KeyResolver::default();
+        assert_eq!(
+            press(&mut resolver, "12j"),
+            [
+                Resolution::Pending,
+                Resolution::Pending,
+                Resolution::Matched("down".to_string(), Some(12))
+            ]
+        );
+        let esc = parse_key("esc").unwrap();
+        let g = parse_key("g").unwrap();
+        assert_eq!(press(&mut resolver, "g"), [Resolution::Pending]);
+        assert_eq!(
+            resolver.resolve(&TestKeyMap::default(), &esc),
+            Resolution::NoMatch(vec![g, esc])
+        );
+        assert!(resolver.pending().is_empty());
+    }
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn parses_keys() {
+        let ctrl_x = key(KeyCode::Char('x'), KeyModifiers::CONTROL);
+        assert_eq!(parse_key("ctrl+x"), Ok(ctrl_x));
+        assert_eq!(parse_key("Control+x"), Ok(ctrl_x));
+        assert_eq!(parse_key("<C-x>"), Ok(ctrl_x));
+        assert_eq!(
+            parse_key("alt+enter"),
+            Ok(key(KeyCode::Enter, KeyModifiers::ALT))
+        );
+        assert_eq!(
+            parse_key("Page Down"),
+            Ok(key(KeyCode::PageDown, KeyModifiers::NONE))
+        );
+        assert_eq!(parse_key("f5"), Ok(key(KeyCode::F(5), KeyModifiers::NONE)));
+        assert_eq!(
+            parse_key("space"),
+            Ok(key(KeyCode::Char(' '), KeyModifiers::NONE))
+        );
+        assert_eq!(
+            parse_key("ctrl++"),
+            Ok(key(KeyCode::Char('+'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(
+            parse_key("shift+g"),
+            Ok(key(KeyCode::Char('G'), KeyModifiers::SHIFT))
+        );
+        assert_eq!(
+            parse_key("G"),
+            Ok(key(KeyCode::Char('G'), KeyModifiers::SHIFT))
+        );
+        assert_eq!(
+            parse_key("<lt>"),
+            Ok(key(KeyCode::Char('<'), KeyModifiers::NONE))
+        );
+        assert!(parse_key("hyperdrive+x").is_err());
+        assert!(parse_key("nokey").is_err());
+    }
+
+    #[test]
+    fn parses_key_sequences() {
+        let g = key(KeyCode::Char('g'), KeyModifiers::NONE);
+        let ctrl = |c| key(KeyCode::Char(c), KeyModifiers::CONTROL);
+        assert_eq!(parse_keys("gg"), Ok(KeySequence(vec![g, g])));
+        assert_eq!(parse_keys("g g"), Ok(KeySequence(vec![g, g])));
+        assert_eq!(
+            parse_keys("ctrl+x ctrl+s"),
+            Ok(KeySequence(vec![ctrl('x'), ctrl('s')]))
+        );
+        assert_eq!(
+            parse_keys("<C-x><C-s>"),
+            Ok(KeySequence(vec![ctrl('x'), ctrl('s')]))
+        );
+        assert_eq!(
+            parse_keys("<space>f"),
+            Ok(KeySequence(vec![
+                key(KeyCode::Char(' '), KeyModifiers::NONE),
+                key(KeyCode::Char('f'), KeyModifiers::NONE)
+            ]))
+        );
+        assert!(parse_keys("").is_err());
+        assert!(parse_keys("ctrl+x nope+s").is_err());
+        assert!(parse_keys("<C-nokey>").is_err());
+    }
+
+    #[test]
+    fn parses_mouse_bindings() {
+        assert_eq!(
+            parse_binding("ctrl+leftdoubleclick"),
+            Ok(Binding::Mouse(
+                MouseBinding::new(MouseAction::Click(MouseButton::Left), KeyModifiers::CONTROL)
+                    .with_clicks(2)
+            ))
+        );
+        assert_eq!(
+            parse_binding("scroll up"),
+            Ok(Binding::Mouse(MouseBinding::new(
+                MouseAction::ScrollUp,
+                KeyModifiers::NONE
+            )))
+        );
+        assert!(matches!(parse_binding("j"), Ok(Binding::Keys(_))));
+        assert!(parse_binding("nope+leftclick").is_err());
+    }
+
+    #[test]
+    fn formats_bindings_that_parse_back() {
+        for input in [
+            "ctrl+x ctrl+s",
+            "gg",
+            "alt+enter",
+            "G",
+            "f12",
+            "space",
+            "ctrl+leftclick",
+        ] {
+            let binding = parse_binding(input).unwrap();
+            assert_eq!(parse_binding(&binding.to_string()), Ok(binding), "{input}");
+        }
+    }
+
+    #[test]
+    fn deserializes_key_bindings() {
+        #[derive(Serialize, Deserialize)]
+        struct Config {
+            keys: KeyBindings<String>,
+        }
+
+        let config: Config = toml::from_str(
+            r#"
+            [keys]
+            down = ["j", "down"]
+            top = "gg"
+            "#,
+        )
+        .unwrap();
+        let shortcuts = |config: &Config| {
+            config
+                .keys
+                .0
+                .iter()
+                .map(|ShortCut(cmd, keys)| {
+                    (cmd.clone(), keys.iter().map(Binding::to_string).join(","))
+                })
+                .collect_vec()
+        };
+        assert_eq!(
+            shortcuts(&config),
+            [
+                ("down".to_string(), "j,down".to_string()),
+                ("top".to_string(), "g g".to_string())
+            ]
+        );
+        let written: Config = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(shortcuts(&written), shortcuts(&config));
+
+        let err = toml::from_str::<Config>("[keys]\ndown = \"ctrl+nokey\"").err();
+        assert!(err.is_some_and(|e| e.to_string().contains("unknown key 'nokey'")));
+    }
+
+    #[test]
+    fn validates_shortcuts() {
+        let bind = |cmd: &str, keys: &[&str]| {
+            ShortCut(
+                cmd.to_string(),
+                keys.iter().map(|k| parse_binding(k).unwrap()).collect(),
+            )
+        };
+        let shortcuts = [
+            bind("down", &["j"]),
+            bind("next", &["j", "n"]),
+            bind("bang", &["shift+1"]),
+            bind("back", &["ctrl+h"]),
+        ];
+        let bang = KeyIssue::Unsendable {
+            binding: parse_binding("shift+1").unwrap(),
+            command: "bang".into(),
+            reason: "shifted symbols are sent as the symbol itself".into(),
+        };
+        assert_eq!(
+            validate_shortcuts(&shortcuts, false),
+            [
+                KeyIssue::Duplicate {
+                    binding: parse_binding("j").unwrap(),
+                    commands: vec!["down".into(), "next".into()],
+                },
+                bang.clone(),
+                KeyIssue::Unsendable {
+                    binding: parse_binding("ctrl+h").unwrap(),
+                    command: "back".into(),
+                    reason: "ctrl+h is sent as backspace".into(),
+                },
+            ]
+        );
+        assert_eq!(validate_shortcuts(&shortcuts[2..], true), [bang]);
+    }
+
+    #[test]
+    fn reports_layer_conflicts() {
+        let layer =
+            |cmd: &str, keys: Binding| KeyBindings(vec![ShortCut(cmd.to_string(), vec![keys])]);
+        // as some terminals report it, built in code so not normalized
+        let bang = key(KeyCode::Char('!'), KeyModifiers::SHIFT).into();
+        let keymap = LayeredKeyMap::<TestKeyMap>::default()
+            .with_layer("app", layer("bang", bang))
+            .with_layer("user", layer("shell", parse_binding("!").unwrap()));
+        assert_eq!(
+            keymap.conflicts(),
+            [KeyConflict {
+                layer: "user".into(),
+                keys: parse_binding("!").unwrap(),
+                command: "shell".into(),
+                previous: "bang".into(),
+            }]
+        );
+        assert!(keymap.get_shortcuts().iter().all(|s| s.0 != "bang"));
+    }
+
+    #[test]
+    fn reports_shadowed_bindings() {
+        let issues = TestKeyMap::default().validate(false);
+        assert_eq!(
+            issues,
+            [KeyIssue::Shadowed {
+                binding: parse_binding("d").unwrap(),
+                command: "delete".into(),
+                by: parse_binding("dd").unwrap(),
+                by_command: "delete line".into(),
+            }]
+        );
+    }
+}
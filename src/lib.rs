@@ -1,7 +1,22 @@
+pub mod bookmarks;
+pub mod cell_renderer;
+pub mod clipboard;
+pub mod column_stats;
 pub mod config;
 pub mod dock;
+pub mod help;
+pub mod hyperlink;
+pub mod inspector;
+pub mod keyboard;
 pub mod keymap;
+pub mod master_detail;
+pub mod plain;
+pub mod query;
 pub mod stateful_table;
 pub mod status_line;
+pub mod style_rule;
+pub mod table_pane;
+pub mod too_small;
 pub mod view;
 pub mod view_controller;
+pub mod which_key;
@@ -0,0 +1,160 @@
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+use ratatui::crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::Frame;
+use serde::{Deserialize, Serialize};
+
+use crate::keymap::{Command, CommandKeyMap, ShortCut};
+use crate::stateful_table::{StatefulTable, Tabular};
+use crate::table_pane::TablePane;
+use crate::view::{KeyHandler, View};
+
+pub type PreviewFn<'a, V> = Box<dyn Fn(&V, &mut Frame, Rect) + 'a>;
+
+pub struct MasterDetail<'a, T: Tabular, M, S, K> {
+    pane: TablePane<'a, T, DetailCommand, S>,
+    preview: PreviewFn<'a, T::Value>,
+    kind: K,
+    direction: Direction,
+    ratio: u16,
+    show_preview: bool,
+    debounce: Duration,
+    pending: Option<T::Value>,
+    pending_since: Instant,
+    previewed: Option<T::Value>,
+    _marker: PhantomData<(M, S)>,
+}
+impl<'a, T: Tabular, M, S, K> MasterDetail<'a, T, M, S, K>
+where
+    T::Value: Clone + PartialEq,
+{
+    pub fn new(
+        table: StatefulTable<'a, T>,
+        preview: impl Fn(&T::Value, &mut Frame, Rect) + 'a,
+        kind: K,
+    ) -> Self {
+        let previewed = table.selected_value().cloned();
+        Self {
+            pane: TablePane::new(table, "Preview"),
+            preview: Box::new(preview),
+            kind,
+            direction: Direction::Horizontal,
+            ratio: 50,
+            show_preview: true,
+            debounce: Duration::from_millis(150),
+            pending: previewed.clone(),
+            pending_since: Instant::now(),
+            previewed,
+            _marker: PhantomData,
+        }
+    }
+    pub fn pane(&self) -> &TablePane<'a, T, DetailCommand, S> {
+        &self.pane
+    }
+    pub fn pane_mut(&mut self) -> &mut TablePane<'a, T, DetailCommand, S> {
+        &mut self.pane
+    }
+    pub fn set_split(&mut self, direction: Direction, ratio: u16) {
+        self.direction = direction;
+        self.ratio = ratio.clamp(0, 100);
+    }
+    pub fn set_debounce(&mut self, debounce: Duration) {
+        self.debounce = debounce;
+    }
+    pub fn toggle_preview(&mut self) {
+        self.show_preview = !self.show_preview;
+    }
+    pub fn is_preview_visible(&self) -> bool {
+        self.show_preview
+    }
+
+    // --- helpers
+    // follows the selected value, so sorting or new data keep the preview on the same row
+    fn update_previewed(&mut self) {
+        let selected = self.pane.table().selected_value();
+        if selected != self.pending.as_ref() {
+            self.pending = selected.cloned();
+            self.pending_since = Instant::now();
+        }
+        if self.pending_since.elapsed() >= self.debounce && self.previewed != self.pending {
+            self.previewed = self.pending.clone();
+        }
+    }
+}
+impl<T: Tabular, M, S, K> View for MasterDetail<'_, T, M, S, K>
+where
+    T::Value: Clone + PartialEq,
+    S: Default,
+    K: PartialEq + Clone,
+{
+    type Model = M;
+    type Signal = S;
+    type Kind = K;
+
+    fn kind(&self) -> Self::Kind {
+        self.kind.clone()
+    }
+    fn key_handler(&self) -> Option<&dyn KeyHandler> {
+        Some(&self.pane)
+    }
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) {
+        if !self.show_preview {
+            self.pane.table_mut().draw(f, area);
+            return;
+        }
+
+        let layout = Layout::default()
+            .direction(self.direction)
+            .constraints([
+                Constraint::Percentage(self.ratio),
+                Constraint::Percentage(100 - self.ratio),
+            ])
+            .split(area);
+        self.pane.table_mut().draw(f, layout[0]);
+
+        self.update_previewed();
+        if let Some(value) = &self.previewed {
+            (self.preview)(value, f, layout[1]);
+        }
+    }
+    fn tick(&mut self) -> Self::Signal {
+        let signal = self.pane.tick();
+        self.update_previewed();
+        signal
+    }
+    fn update(&mut self, ev: &Event) -> Self::Signal {
+        if let Some(DetailCommand::TogglePreview) = self.pane.command(ev) {
+            self.toggle_preview();
+            return S::default();
+        }
+        let signal = self.pane.update(ev);
+        self.update_previewed();
+        signal
+    }
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DetailCommand {
+    TogglePreview,
+}
+impl Display for DetailCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DetailCommand::TogglePreview => write!(f, "toggle preview"),
+        }
+    }
+}
+impl Command for DetailCommand {
+    fn default_bindings() -> Vec<ShortCut<Self>> {
+        vec![ShortCut(
+            DetailCommand::TogglePreview,
+            vec![KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE).into()],
+        )]
+    }
+}
+
+pub type DetailKeyMap = CommandKeyMap<DetailCommand>;
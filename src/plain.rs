@@ -0,0 +1,189 @@
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::Span;
+use ratatui::widgets::{Block, StatefulWidget, TableState};
+
+use crate::stateful_table::{Padding, StatefulTable, TableStyle, Tabular};
+
+#[derive(Clone, Copy, Debug)]
+pub struct PlainOptions {
+    pub borders: bool,
+    pub colors: bool,
+    pub column_spacing: u16,
+    pub header: Style,
+}
+impl Default for PlainOptions {
+    fn default() -> Self {
+        Self {
+            borders: false,
+            colors: false,
+            column_spacing: 2,
+            header: Style::new().bold(),
+        }
+    }
+}
+
+pub fn render_plain<T: Tabular>(data: &[T], width: u16, options: &PlainOptions) -> String {
+    let block = if options.borders {
+        let padding = Padding {
+            t: 1,
+            r: 1,
+            b: 1,
+            l: 1,
+        };
+        (Block::bordered(), padding)
+    } else {
+        (Block::new(), Padding::default())
+    };
+    let style = TableStyle {
+        header: options.header,
+        block,
+        column_spacing: options.column_spacing,
+        ..Default::default()
+    };
+    let mut table = StatefulTable::new(data.to_vec(), TableState::new(), style, None);
+    table.set_hyperlinks(false);
+
+    let (min_width, height) = table.min_area();
+    let width = if width == 0 { min_width } else { width };
+    let area = Rect::new(0, 0, width, height);
+    let mut buf = Buffer::empty(area);
+    table.render(area, &mut buf, &mut TableState::new());
+    to_string(&buf, options.colors)
+}
+
+fn to_string(buf: &Buffer, colors: bool) -> String {
+    let area = buf.area;
+    let mut lines = vec![];
+    for y in area.top()..area.bottom() {
+        // a wide symbol covers the cells after it, which only hold placeholders
+        let mut cells = vec![];
+        let mut x = area.left();
+        while x < area.right() {
+            let cell = &buf[(x, y)];
+            x += Span::raw(cell.symbol()).width().max(1) as u16;
+            if !cell.skip {
+                cells.push(cell);
+            }
+        }
+        let end = cells
+            .iter()
+            .rposition(|cell| cell.symbol() != " " || (colors && cell.bg != Color::Reset))
+            .map_or(0, |pos| pos + 1);
+
+        let mut line = String::new();
+        let reset = Cell::default().style();
+        let mut curr = reset;
+        for cell in &cells[..end] {
+            let style = cell.style();
+            if colors && style != curr {
+                line.push_str(&sgr(style));
+                curr = style;
+            }
+            line.push_str(cell.symbol());
+        }
+        if colors && curr != reset {
+            line.push_str("\x1b[0m");
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+fn sgr(style: Style) -> String {
+    let mut codes = vec!["0".to_string()];
+    let modifiers = [
+        (Modifier::BOLD, "1"),
+        (Modifier::DIM, "2"),
+        (Modifier::ITALIC, "3"),
+        (Modifier::UNDERLINED, "4"),
+        (Modifier::SLOW_BLINK, "5"),
+        (Modifier::RAPID_BLINK, "6"),
+        (Modifier::REVERSED, "7"),
+        (Modifier::HIDDEN, "8"),
+        (Modifier::CROSSED_OUT, "9"),
+    ];
+    for (modifier, code) in modifiers {
+        if style.add_modifier.contains(modifier) {
+            codes.push(code.into());
+        }
+    }
+    if let Some(fg) = style.fg.filter(|c| *c != Color::Reset) {
+        codes.push(color_code(fg, false));
+    }
+    if let Some(bg) = style.bg.filter(|c| *c != Color::Reset) {
+        codes.push(color_code(bg, true));
+    }
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+fn color_code(color: Color, bg: bool) -> String {
+    let base = if bg { 10 } else { 0 };
+    match color {
+        Color::Rgb(r, g, b) => format!("{};2;{r};{g};{b}", 38 + base),
+        Color::Indexed(i) => format!("{};5;{i}", 38 + base),
+        color => {
+            let code = match color {
+                Color::Black => 30,
+                Color::Red => 31,
+                Color::Green => 32,
+                Color::Yellow => 33,
+                Color::Blue => 34,
+                Color::Magenta => 35,
+                Color::Cyan => 36,
+                Color::Gray => 37,
+                Color::DarkGray => 90,
+                Color::LightRed => 91,
+                Color::LightGreen => 92,
+                Color::LightYellow => 93,
+                Color::LightBlue => 94,
+                Color::LightMagenta => 95,
+                Color::LightCyan => 96,
+                Color::White => 97,
+                _ => 39,
+            };
+            (code + base).to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::layout::Constraint;
+
+    use super::*;
+
+    #[derive(Clone)]
+    struct Item(&'static str, &'static str);
+    impl Tabular for Item {
+        type Value = String;
+
+        fn value(&self) -> Self::Value {
+            self.0.into()
+        }
+        fn content(&self) -> Vec<String> {
+            vec![self.0.into(), self.1.into()]
+        }
+        fn column_constraints() -> Vec<fn(u16) -> Constraint> {
+            vec![Constraint::Length, Constraint::Length]
+        }
+        fn column_names() -> Option<Vec<String>> {
+            Some(vec!["Name".into(), "City".into()])
+        }
+    }
+
+    #[test]
+    fn renders_plain_text() {
+        let data = [Item("ann", "Oslo"), Item("bob", "Rome")];
+        let plain = render_plain(&data, 0, &PlainOptions::default());
+        assert_eq!(plain, "Name   City\nann    Oslo\nbob    Rome");
+    }
+
+    #[test]
+    fn skips_cells_covered_by_wide_characters() {
+        let data = [Item("東京", "Tokyo"), Item("ann", "Oslo")];
+        let plain = render_plain(&data, 0, &PlainOptions::default());
+        assert_eq!(plain, "Name   City\n東京   Tokyo\nann    Oslo");
+    }
+}
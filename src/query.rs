@@ -0,0 +1,504 @@
+use std::cmp::Ordering;
+use std::fmt::Display;
+
+use regex::{Regex, RegexBuilder};
+
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+pub enum ColumnKind {
+    #[default]
+    Text,
+    Number,
+    Size,
+    Duration,
+}
+impl ColumnKind {
+    pub fn parse(&self, value: &str) -> Option<f64> {
+        let value = value.trim();
+        match self {
+            ColumnKind::Text => None,
+            ColumnKind::Number => value.trim_end_matches('%').parse().ok(),
+            ColumnKind::Size => parse_size(value),
+            ColumnKind::Duration => parse_duration(value),
+        }
+    }
+}
+impl Display for ColumnKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColumnKind::Text => write!(f, "text"),
+            ColumnKind::Number => write!(f, "number"),
+            ColumnKind::Size => write!(f, "size"),
+            ColumnKind::Duration => write!(f, "duration"),
+        }
+    }
+}
+
+fn parse_size(value: &str) -> Option<f64> {
+    let value = value.trim_end_matches(['b', 'B']).trim_end_matches('i');
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    let (num, unit) = value.split_at(split);
+    let mult = match unit.trim().to_ascii_uppercase().as_str() {
+        "" => 1f64,
+        "K" => 1024f64,
+        "M" => 1024f64.powi(2),
+        "G" => 1024f64.powi(3),
+        "T" => 1024f64.powi(4),
+        _ => return None,
+    };
+    num.parse::<f64>().ok().map(|n| n * mult)
+}
+
+fn parse_duration(value: &str) -> Option<f64> {
+    let mut total = 0f64;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let split = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let (num, tail) = rest.split_at(split);
+        let num = num.parse::<f64>().ok()?;
+        let unit_len = tail
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_len);
+        total += num
+            * match unit.trim() {
+                "ms" => 0.001,
+                "" | "s" => 1f64,
+                "m" => 60f64,
+                "h" => 3600f64,
+                "d" => 86400f64,
+                _ => return None,
+            };
+        rest = tail;
+    }
+    Some(total)
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct QueryError {
+    pub pos: usize,
+    pub msg: String,
+}
+impl QueryError {
+    fn new(pos: usize, msg: impl Into<String>) -> Self {
+        Self {
+            pos,
+            msg: msg.into(),
+        }
+    }
+}
+impl Display for QueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at position {}", self.msg, self.pos)
+    }
+}
+impl std::error::Error for QueryError {}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Op {
+    Contains,
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Matches,
+}
+impl Op {
+    fn accepts(&self, ord: Ordering) -> bool {
+        match self {
+            Op::Contains | Op::Eq | Op::Matches => ord == Ordering::Equal,
+            Op::Ne => ord != Ordering::Equal,
+            Op::Gt => ord == Ordering::Greater,
+            Op::Ge => ord != Ordering::Less,
+            Op::Lt => ord == Ordering::Less,
+            Op::Le => ord != Ordering::Greater,
+        }
+    }
+}
+
+#[derive(Clone)]
+enum Value {
+    Text(String),
+    Number(f64),
+    Regex(Regex),
+}
+
+#[derive(Clone)]
+enum Expr {
+    And(Vec<Expr>),
+    Or(Vec<Expr>),
+    Not(Box<Expr>),
+    Any(String),
+    Term {
+        col: usize,
+        kind: ColumnKind,
+        op: Op,
+        value: Value,
+    },
+}
+impl Expr {
+    fn matches(&self, content: &[String]) -> bool {
+        match self {
+            Expr::And(exprs) => exprs.iter().all(|e| e.matches(content)),
+            Expr::Or(exprs) => exprs.iter().any(|e| e.matches(content)),
+            Expr::Not(expr) => !expr.matches(content),
+            Expr::Any(needle) => content.iter().any(|c| c.to_lowercase().contains(needle)),
+            Expr::Term {
+                col,
+                kind,
+                op,
+                value,
+            } => {
+                let Some(cell) = content.get(*col) else {
+                    return false;
+                };
+                match value {
+                    Value::Regex(regex) => regex.is_match(cell),
+                    Value::Number(num) => kind
+                        .parse(cell)
+                        .and_then(|n| n.partial_cmp(num))
+                        .is_some_and(|ord| op.accepts(ord)),
+                    Value::Text(text) => {
+                        let cell = cell.to_lowercase();
+                        match op {
+                            Op::Contains => cell.contains(text),
+                            _ => op.accepts(cell.as_str().cmp(text)),
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Query {
+    input: String,
+    expr: Expr,
+}
+impl Query {
+    pub fn parse(input: &str, names: &[String], kinds: &[ColumnKind]) -> Result<Self, QueryError> {
+        let tokens = tokenize(input)?;
+        let mut parser = Parser {
+            tokens,
+            idx: 0,
+            end: input.chars().count(),
+            names,
+            kinds,
+        };
+        let expr = parser.parse_or()?;
+        match parser.peek() {
+            Some((pos, _)) => Err(QueryError::new(*pos, "unexpected ')'")),
+            None => Ok(Self {
+                input: input.to_string(),
+                expr,
+            }),
+        }
+    }
+    pub fn matches(&self, content: &[String]) -> bool {
+        self.expr.matches(content)
+    }
+}
+impl Display for Query {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.input)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Token {
+    LParen,
+    RParen,
+    Not,
+    Or,
+    And,
+    Word(String),
+}
+
+fn tokenize(input: &str) -> Result<Vec<(usize, Token)>, QueryError> {
+    let chars = input.chars().collect::<Vec<_>>();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '(' => {
+                tokens.push((i, Token::LParen));
+                i += 1;
+            }
+            ')' => {
+                tokens.push((i, Token::RParen));
+                i += 1;
+            }
+            '|' => {
+                tokens.push((i, Token::Or));
+                i += 1;
+            }
+            '-' => {
+                tokens.push((i, Token::Not));
+                i += 1;
+            }
+            _ => {
+                let start = i;
+                let mut word = String::new();
+                while i < chars.len() {
+                    match chars[i] {
+                        c if c.is_whitespace() || c == '(' || c == ')' => break,
+                        '"' => {
+                            let quote = i;
+                            word.push('"');
+                            i += 1;
+                            while i < chars.len() && chars[i] != '"' {
+                                word.push(chars[i]);
+                                i += 1;
+                            }
+                            if i == chars.len() {
+                                return Err(QueryError::new(quote, "unterminated quote"));
+                            }
+                            word.push('"');
+                            i += 1;
+                        }
+                        c => {
+                            word.push(c);
+                            i += 1;
+                        }
+                    }
+                }
+                let token = match word.as_str() {
+                    "OR" => Token::Or,
+                    "AND" => Token::And,
+                    _ => Token::Word(word),
+                };
+                tokens.push((start, token));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: Vec<(usize, Token)>,
+    idx: usize,
+    end: usize,
+    names: &'a [String],
+    kinds: &'a [ColumnKind],
+}
+impl Parser<'_> {
+    fn peek(&self) -> Option<&(usize, Token)> {
+        self.tokens.get(self.idx)
+    }
+    fn next(&mut self) -> Option<(usize, Token)> {
+        let token = self.tokens.get(self.idx).cloned();
+        self.idx += 1;
+        token
+    }
+    fn parse_or(&mut self) -> Result<Expr, QueryError> {
+        let mut queries = vec![self.parse_and()?];
+        while let Some((_, Token::Or)) = self.peek() {
+            self.next();
+            queries.push(self.parse_and()?);
+        }
+        Ok(match queries.len() {
+            1 => queries.remove(0),
+            _ => Expr::Or(queries),
+        })
+    }
+    fn parse_and(&mut self) -> Result<Expr, QueryError> {
+        let mut queries = vec![self.parse_unary()?];
+        loop {
+            match self.peek() {
+                Some((_, Token::And)) => {
+                    self.next();
+                }
+                Some((_, Token::Or | Token::RParen)) | None => break,
+                _ => {}
+            }
+            queries.push(self.parse_unary()?);
+        }
+        Ok(match queries.len() {
+            1 => queries.remove(0),
+            _ => Expr::And(queries),
+        })
+    }
+    fn parse_unary(&mut self) -> Result<Expr, QueryError> {
+        match self.next() {
+            Some((_, Token::Not)) => Ok(Expr::Not(Box::new(self.parse_unary()?))),
+            Some((pos, Token::LParen)) => {
+                let query = self.parse_or()?;
+                match self.next() {
+                    Some((_, Token::RParen)) => Ok(query),
+                    _ => Err(QueryError::new(pos, "unclosed '('")),
+                }
+            }
+            Some((pos, Token::Word(word))) => self.parse_term(pos, &word),
+            Some((pos, _)) => Err(QueryError::new(pos, "expected a term")),
+            None => Err(QueryError::new(self.end, "expected a term")),
+        }
+    }
+    fn parse_term(&self, pos: usize, word: &str) -> Result<Expr, QueryError> {
+        let Some(op_start) = find_operator(word) else {
+            return Ok(Expr::Any(unquote(word).to_lowercase()));
+        };
+        let (name, rest) = word.split_at(op_start);
+        let quoted_name = name;
+        let name = unquote(name);
+        let (op, op_len) = match rest {
+            r if r.starts_with("!=") => (Op::Ne, 2),
+            r if r.starts_with(">=") => (Op::Ge, 2),
+            r if r.starts_with("<=") => (Op::Le, 2),
+            r if r.starts_with(':') => (Op::Contains, 1),
+            r if r.starts_with('=') => (Op::Eq, 1),
+            r if r.starts_with('>') => (Op::Gt, 1),
+            r if r.starts_with('<') => (Op::Lt, 1),
+            r if r.starts_with('~') => (Op::Matches, 1),
+            _ => {
+                let op_pos = pos + quoted_name.chars().count();
+                return Err(QueryError::new(op_pos, "invalid operator"));
+            }
+        };
+        let col = self
+            .names
+            .iter()
+            .position(|n| n.eq_ignore_ascii_case(&name))
+            .ok_or_else(|| QueryError::new(pos, format!("unknown column '{name}'")))?;
+        let kind = self.kinds.get(col).copied().unwrap_or_default();
+
+        let value_pos = pos + quoted_name.chars().count() + op_len;
+        let raw_value = &rest[op_len..];
+        if raw_value.is_empty() {
+            return Err(QueryError::new(value_pos, "missing value"));
+        }
+        let value = unquote(raw_value);
+
+        let value = match (op, kind) {
+            // `name=""` finds empty cells of any kind
+            (Op::Eq | Op::Ne, _) if value.is_empty() => Value::Text(value),
+            (Op::Matches, _) => RegexBuilder::new(&value)
+                .case_insensitive(true)
+                .build()
+                .map(Value::Regex)
+                .map_err(|_| QueryError::new(value_pos, "invalid regex"))?,
+            (_, ColumnKind::Text) => Value::Text(value.to_lowercase()),
+            (_, kind) => kind
+                .parse(&value)
+                .map(Value::Number)
+                .ok_or_else(|| QueryError::new(value_pos, format!("invalid {kind} '{value}'")))?,
+        };
+        Ok(Expr::Term {
+            col,
+            kind,
+            op,
+            value,
+        })
+    }
+}
+
+// quoted operators are part of the name or value, as in `"first name"=bob` or `"10:30"`
+fn find_operator(word: &str) -> Option<usize> {
+    let mut quoted = false;
+    word.char_indices().find_map(|(idx, c)| match c {
+        '"' => {
+            quoted = !quoted;
+            None
+        }
+        ':' | '=' | '!' | '<' | '>' | '~' if !quoted => Some(idx),
+        _ => None,
+    })
+}
+
+fn unquote(word: &str) -> String {
+    word.replace('"', "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(input: &str) -> Result<Query, QueryError> {
+        let names = ["name", "size", "age", "first name", "time"].map(String::from);
+        let kinds = [
+            ColumnKind::Text,
+            ColumnKind::Size,
+            ColumnKind::Duration,
+            ColumnKind::Text,
+            ColumnKind::Text,
+        ];
+        Query::parse(input, &names, &kinds)
+    }
+    fn matches(input: &str, row: [&str; 5]) -> bool {
+        query(input).unwrap().matches(&row.map(String::from))
+    }
+    fn error(input: &str) -> (usize, String) {
+        let err = query(input).err().unwrap();
+        (err.pos, err.msg)
+    }
+
+    const ROW: [&str; 5] = ["bob", "1.5M", "2h", "Robert", "10:30"];
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        assert!(matches("alice | bob robert", ROW));
+        assert!(!matches("alice | bob carol", ROW));
+        assert!(matches("(alice | bob) robert", ROW));
+        assert!(!matches("(alice | bob) carol", ROW));
+        assert!(matches("bob AND robert OR carol", ROW));
+    }
+
+    #[test]
+    fn negates_terms_and_groups() {
+        assert!(!matches("-bob", ROW));
+        assert!(matches("-alice", ROW));
+        assert!(matches("-(alice | carol)", ROW));
+        assert!(!matches("- -alice", ROW));
+    }
+
+    #[test]
+    fn compares_typed_columns() {
+        assert!(matches("size>1M", ROW));
+        assert!(!matches("size>=2M", ROW));
+        assert!(matches("size<2048K", ROW));
+        assert!(matches("age>=90m", ROW));
+        assert!(!matches("age<1h30m", ROW));
+        assert!(matches("name=BOB", ROW));
+        assert!(matches("name!=alice", ROW));
+        assert!(matches("name~^b.b$", ROW));
+        assert!(matches("name:o", ROW));
+    }
+
+    #[test]
+    fn keeps_quoted_operators() {
+        assert!(matches("\"10:30\"", ROW));
+        assert!(matches("time=\"10:30\"", ROW));
+        assert!(matches("\"first name\"=robert", ROW));
+        assert!(matches("\"first name\":\"rob\"", ROW));
+    }
+
+    #[test]
+    fn matches_empty_values() {
+        let empty = ["", "", "", "", ""];
+        assert!(matches("name=\"\"", empty));
+        assert!(matches("size=\"\"", empty));
+        assert!(!matches("name=\"\"", ROW));
+        assert!(matches("age!=\"\"", ROW));
+    }
+
+    #[test]
+    fn reports_error_positions() {
+        assert_eq!(error("bob owner=x"), (4, "unknown column 'owner'".into()));
+        assert_eq!(error("name="), (5, "missing value".into()));
+        assert_eq!(error("size>lots"), (5, "invalid size 'lots'".into()));
+        assert_eq!(error("name~\"(\""), (5, "invalid regex".into()));
+        assert_eq!(error("(bob"), (0, "unclosed '('".into()));
+        assert_eq!(error("bob)"), (3, "unexpected ')'".into()));
+        assert_eq!(error("a | "), (4, "expected a term".into()));
+        assert_eq!(error("x \"ab"), (2, "unterminated quote".into()));
+        assert_eq!(error("name!x"), (4, "invalid operator".into()));
+        assert_eq!(error("\"first name\"!x"), (12, "invalid operator".into()));
+    }
+}
@@ -1,6 +1,9 @@
 use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Display;
+use std::hash::Hash;
 use std::ops::Div;
+use std::time::{Duration, Instant};
 use std::vec;
 
 use itertools::Itertools;
@@ -8,13 +11,29 @@ use ratatui::buffer::Buffer;
 use ratatui::crossterm::event::{
     Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEventKind,
 };
-use ratatui::layout::{Alignment, Constraint, Layout, Position, Rect};
+use ratatui::layout::{Alignment, Constraint, Flex, Layout, Position, Rect};
 use ratatui::style::Style;
-use ratatui::text::Text;
-use ratatui::widgets::{Block, Row, StatefulWidget, Table, TableState};
+use ratatui::text::{Span, Text};
+use ratatui::widgets::{Block, Cell, Clear, Row, StatefulWidget, Table, TableState, Widget};
 use ratatui::Frame;
+use serde::{Deserialize, Serialize};
 
-use crate::keymap::{KeyMap, ShortCut};
+use crate::bookmarks::Bookmark;
+use crate::cell_renderer::CellRenderer;
+use crate::clipboard;
+use crate::column_stats::ColumnStats;
+use crate::config::deserialize_style;
+use crate::help::{help_entries, HelpEntry};
+use crate::hyperlink;
+use crate::keyboard;
+use crate::keymap::{
+    ClickCounter, Command, KeyMap, KeyResolver, KeySequence, Mode, MouseAction, MouseBinding,
+    Resolution, ShortCut,
+};
+use crate::query::{ColumnKind, Query, QueryError};
+use crate::style_rule::{RuleScope, StyleRule};
+use crate::view::KeyHandler;
+use crate::which_key::KeyHints;
 
 #[derive(Default, Clone, Copy)]
 pub struct Padding {
@@ -58,6 +77,15 @@ pub trait Tabular: Clone {
     fn column_alignments() -> Option<Vec<Alignment>> {
         None
     }
+    fn column_kinds() -> Option<Vec<ColumnKind>> {
+        None
+    }
+    fn cell_renderer(&self, _col: usize) -> Option<CellRenderer> {
+        None
+    }
+    fn cell_link(&self, _col: usize) -> Option<String> {
+        None
+    }
     fn row_height() -> u16 {
         1
     }
@@ -66,7 +94,10 @@ pub trait Tabular: Clone {
     }
 }
 
+// built with `TableStyle::default()` and the setters below, so new styles can
+// be added without breaking callers
 #[derive(Default)]
+#[non_exhaustive]
 pub struct TableStyle<'a> {
     pub table: Style,
     pub header: Style,
@@ -74,27 +105,265 @@ pub struct TableStyle<'a> {
     pub highlight: Style,
     pub col_highlight: Style,
     pub normal: Style,
+    pub zebra: Style,
+    pub marked: Style,
+    pub changed: Style,
+    pub added: Style,
+    pub removed: Style,
+    pub group: Style,
+    pub loading: Style,
+    pub empty: Style,
+    pub error: Style,
+    // precedence: zebra < Tabular::style < row rules < marked/added < cell rules < style fn < changed
+    pub rules: Vec<StyleRule>,
+    pub column_spacing: u16,
+}
+
+impl<'a> TableStyle<'a> {
+    pub fn table(mut self, style: Style) -> Self {
+        self.table = style;
+        self
+    }
+    pub fn header(mut self, style: Style) -> Self {
+        self.header = style;
+        self
+    }
+    pub fn highlight(mut self, style: Style) -> Self {
+        self.highlight = style;
+        self
+    }
+    pub fn col_highlight(mut self, style: Style) -> Self {
+        self.col_highlight = style;
+        self
+    }
+    pub fn normal(mut self, style: Style) -> Self {
+        self.normal = style;
+        self
+    }
+    pub fn zebra(mut self, style: Style) -> Self {
+        self.zebra = style;
+        self
+    }
+    pub fn marked(mut self, style: Style) -> Self {
+        self.marked = style;
+        self
+    }
+    pub fn changed(mut self, style: Style) -> Self {
+        self.changed = style;
+        self
+    }
+    pub fn added(mut self, style: Style) -> Self {
+        self.added = style;
+        self
+    }
+    pub fn removed(mut self, style: Style) -> Self {
+        self.removed = style;
+        self
+    }
+    pub fn group(mut self, style: Style) -> Self {
+        self.group = style;
+        self
+    }
+    pub fn loading(mut self, style: Style) -> Self {
+        self.loading = style;
+        self
+    }
+    pub fn empty(mut self, style: Style) -> Self {
+        self.empty = style;
+        self
+    }
+    pub fn error(mut self, style: Style) -> Self {
+        self.error = style;
+        self
+    }
+    pub fn block(mut self, block: Block<'a>, padding: Padding) -> Self {
+        self.block = (block, padding);
+        self
+    }
+    pub fn rules(mut self, rules: Vec<StyleRule>) -> Self {
+        self.rules = rules;
+        self
+    }
+    pub fn column_spacing(mut self, spacing: u16) -> Self {
+        self.column_spacing = spacing;
+        self
+    }
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+#[non_exhaustive]
+pub struct TableTheme {
+    #[serde(deserialize_with = "deserialize_style")]
+    pub table: Style,
+    #[serde(deserialize_with = "deserialize_style")]
+    pub header: Style,
+    #[serde(deserialize_with = "deserialize_style")]
+    pub highlight: Style,
+    #[serde(deserialize_with = "deserialize_style")]
+    pub col_highlight: Style,
+    #[serde(deserialize_with = "deserialize_style")]
+    pub normal: Style,
+    #[serde(deserialize_with = "deserialize_style")]
+    pub zebra: Style,
+    #[serde(deserialize_with = "deserialize_style")]
+    pub marked: Style,
+    #[serde(deserialize_with = "deserialize_style")]
+    pub changed: Style,
+    #[serde(deserialize_with = "deserialize_style")]
+    pub added: Style,
+    #[serde(deserialize_with = "deserialize_style")]
+    pub removed: Style,
+    #[serde(deserialize_with = "deserialize_style")]
+    pub group: Style,
+    #[serde(deserialize_with = "deserialize_style")]
+    pub loading: Style,
+    #[serde(deserialize_with = "deserialize_style")]
+    pub empty: Style,
+    #[serde(deserialize_with = "deserialize_style")]
+    pub error: Style,
+    pub rules: Vec<StyleRule>,
     pub column_spacing: u16,
 }
+impl From<TableTheme> for TableStyle<'_> {
+    fn from(theme: TableTheme) -> Self {
+        Self {
+            table: theme.table,
+            header: theme.header,
+            block: (Block::default(), Padding::default()),
+            highlight: theme.highlight,
+            col_highlight: theme.col_highlight,
+            normal: theme.normal,
+            zebra: theme.zebra,
+            marked: theme.marked,
+            changed: theme.changed,
+            added: theme.added,
+            removed: theme.removed,
+            group: theme.group,
+            loading: theme.loading,
+            empty: theme.empty,
+            error: theme.error,
+            rules: theme.rules,
+            column_spacing: theme.column_spacing,
+        }
+    }
+}
+
+pub type StyleFn<'a, T> = Box<dyn Fn(&T, usize) -> Option<Style> + 'a>;
+pub type RowKeyFn<'a, T> = Box<dyn Fn(&T) -> String + 'a>;
+pub type AggregateFn<'a, T> = Box<dyn Fn(&[&T], usize) -> Option<String> + 'a>;
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub enum TableStatus {
+    #[default]
+    Ready,
+    Loading,
+    Error(String),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum GroupBy {
+    Key(usize),
+    Cmp(usize),
+}
+impl GroupBy {
+    pub fn col(&self) -> usize {
+        match self {
+            GroupBy::Key(col) | GroupBy::Cmp(col) => *col,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Group {
+    pub key: String,
+    pub col: usize,
+    pub count: usize,
+    pub collapsed: bool,
+    pub aggregates: Vec<Option<String>>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Entry {
+    Group(usize),
+    Row(usize),
+    // a removed row, fading out where it was
+    Ghost(usize),
+}
+
+#[derive(PartialEq, Eq, Hash)]
+enum RowKey<V> {
+    Custom(String),
+    Value(V),
+}
+
+// a removed row, fading out at the row it was shown at within its group
+struct Ghost<T> {
+    row: T,
+    at: usize,
+    group: Option<String>,
+    since: Instant,
+}
+
+#[derive(Clone)]
+enum Change {
+    Added,
+    Cells(Vec<usize>),
+}
 
 pub struct StatefulTable<'a, T: Tabular> {
     data: Vec<T>,
     table: Table<'a>,
     state: TableState,
     style: TableStyle<'a>,
+    sort_col: Option<usize>,
     selected_col_ord: Ordering,
     area: Rect,
-    values: Vec<T::Value>,
+    entries: Vec<Entry>,
+    values: Vec<Option<T::Value>>,
+    // the position of each entry among the data rows, for zebra stripes
+    positions: Vec<usize>,
     keymap: TableKeyMap,
     padding: Padding,
     inner_width: u16,
     col_constraints: Vec<Constraint>,
     indexed: bool,
+    style_fn: Option<StyleFn<'a, T>>,
+    cell_mode: bool,
+    marked: HashSet<usize>,
+    title: Option<String>,
+    query: Option<Query>,
+    row_key: Option<RowKeyFn<'a, T>>,
+    changes: HashMap<usize, (Change, Instant)>,
+    ghosts: Vec<Ghost<T>>,
+    change_fade: Duration,
+    group_by: Option<GroupBy>,
+    groups: Vec<Group>,
+    collapsed: HashSet<String>,
+    aggregate_fn: Option<AggregateFn<'a, T>>,
+    skip_group_headers: bool,
+    hyperlinks: bool,
+    link_opener: Vec<String>,
+    status: TableStatus,
+    status_since: Instant,
+    empty_message: String,
+    follow: bool,
+    following: bool,
+    unseen: usize,
+    bookmarks: BTreeMap<char, usize>,
+    pending_mark: Option<TableCommand>,
+    resolver: KeyResolver,
+    clicks: ClickCounter,
+    modes: Option<Vec<Mode>>,
 }
 impl<'a, T: Tabular> StatefulTable<'a, T> {
     const SORT_ASC: char = '▲';
     const SORT_NONE: char = '-';
     const SORT_DESC: char = '▼';
+    const GROUP_EXPANDED: char = '▾';
+    const GROUP_COLLAPSED: char = '▸';
+    const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+    const SPINNER_FRAME: Duration = Duration::from_millis(80);
 
     pub fn new(
         data: Vec<T>,
@@ -113,7 +382,48 @@ impl<'a, T: Tabular> StatefulTable<'a, T> {
         StatefulTable::build_table(IndexedRow::from(data), state, style, title, true)
     }
     pub fn selected_value(&self) -> Option<&T::Value> {
-        self.state.selected().and_then(|i| self.values.get(i))
+        self.state.selected().and_then(|i| self.value_at(i))
+    }
+    pub fn value_at(&self, row: usize) -> Option<&T::Value> {
+        self.values.get(row).and_then(Option::as_ref)
+    }
+    pub fn selected_group(&self) -> Option<&Group> {
+        match self.entries.get(self.selected_row()?) {
+            Some(Entry::Group(group)) => self.groups.get(*group),
+            _ => None,
+        }
+    }
+    pub fn groups(&self) -> &[Group] {
+        &self.groups
+    }
+    pub fn set_group_by(&mut self, group_by: Option<GroupBy>) {
+        self.group_by = group_by;
+        self.collapsed.clear();
+        self.refresh_rows();
+    }
+    pub fn set_group_aggregate(&mut self, f: impl Fn(&[&T], usize) -> Option<String> + 'a) {
+        self.aggregate_fn = Some(Box::new(f));
+        self.refresh_rows();
+    }
+    pub fn set_skip_group_headers(&mut self, skip: bool) {
+        self.skip_group_headers = skip;
+    }
+    pub fn toggle_group(&mut self) {
+        let Some(row) = self.selected_row() else {
+            return;
+        };
+        let header = (0..=row).rev().find_map(|r| match self.entries.get(r) {
+            Some(Entry::Group(group)) => Some((r, *group)),
+            _ => None,
+        });
+        if let Some((header, group)) = header {
+            let key = self.groups[group].key.clone();
+            if !self.collapsed.remove(&key) {
+                self.collapsed.insert(key);
+            }
+            self.refresh_rows();
+            self.select_absolute(header);
+        }
     }
     pub fn selected_row(&self) -> Option<usize> {
         self.state.selected()
@@ -121,92 +431,571 @@ impl<'a, T: Tabular> StatefulTable<'a, T> {
     pub fn selected_col(&self) -> Option<usize> {
         self.state.selected_column()
     }
+    pub fn sort_col(&self) -> Option<(usize, Ordering)> {
+        self.sort_col.map(|col| (col, self.selected_col_ord))
+    }
+    pub fn is_cell_mode(&self) -> bool {
+        self.cell_mode
+    }
+    pub fn toggle_cell_mode(&mut self) {
+        self.cell_mode = !self.cell_mode;
+        if self.cell_mode && self.selected_col().is_none() {
+            self.select_absolute_col(usize::from(self.indexed));
+        }
+        self.table = std::mem::take(&mut self.table)
+            .column_highlight_style(self.col_highlight())
+            .cell_highlight_style(self.cell_highlight());
+    }
+    pub fn toggle_mark(&mut self) {
+        if let Some(idx) = self.selected_row().and_then(|row| self.data_idx(row)) {
+            if !self.marked.remove(&idx) {
+                self.marked.insert(idx);
+            }
+            self.refresh_rows();
+        }
+    }
+    pub fn clear_marks(&mut self) {
+        self.marked.clear();
+        self.refresh_rows();
+    }
+    pub fn marked_values(&self) -> Vec<&T::Value> {
+        self.marked_rows()
+            .filter_map(|row| self.value_at(row))
+            .collect()
+    }
+    // the first data column when no column is selected, as in row mode
+    pub fn selected_cell_content(&self) -> Option<String> {
+        let col = self.selected_col().unwrap_or(usize::from(self.indexed));
+        self.selected_content()?.into_iter().nth(col)
+    }
+    pub fn selected_row_content(&self) -> Option<String> {
+        self.selected_content().map(|content| content.join("\t"))
+    }
+    pub fn marked_rows_content(&self) -> Option<String> {
+        if self.marked.is_empty() {
+            return self.selected_row_content();
+        }
+        Some(
+            self.marked_rows()
+                .filter_map(|row| self.data_idx(row))
+                .map(|idx| self.data[idx].content().join("\t"))
+                .join("\n"),
+        )
+    }
+    pub fn column_stats(&self, top: usize) -> ColumnStats {
+        let col = self.selected_col().unwrap_or(usize::from(self.indexed));
+        let name = T::column_names().and_then(|names| names.get(col).cloned());
+        let kind = T::column_kinds()
+            .and_then(|kinds| kinds.get(col).copied())
+            .unwrap_or_default();
+        // rows of collapsed groups count too
+        let values = self
+            .filtered_rows()
+            .into_iter()
+            .filter_map(|idx| self.data[idx].content().get(col).cloned())
+            .collect_vec();
+        ColumnStats::new(col, name, kind, &values, top)
+    }
+    pub fn set_bookmark(&mut self, name: char) {
+        if let Some(idx) = self.selected_row().and_then(|row| self.data_idx(row)) {
+            self.bookmarks.insert(name, idx);
+            self.refresh_layout();
+            self.refresh_rows();
+        }
+    }
+    pub fn remove_bookmark(&mut self, name: char) {
+        if self.bookmarks.remove(&name).is_some() {
+            self.refresh_layout();
+            self.refresh_rows();
+        }
+    }
+    pub fn clear_bookmarks(&mut self) {
+        self.bookmarks.clear();
+        self.refresh_layout();
+        self.refresh_rows();
+    }
+    pub fn bookmarks(&self) -> Vec<Bookmark> {
+        self.bookmarks
+            .iter()
+            .map(|(name, idx)| Bookmark {
+                name: *name,
+                row: self.bookmark_row(*idx),
+                content: self.data[*idx].content()[usize::from(self.indexed)..].join(" "),
+            })
+            .collect()
+    }
+    pub fn jump_to_bookmark(&mut self, name: char) -> bool {
+        match self
+            .bookmarks
+            .get(&name)
+            .and_then(|idx| self.bookmark_row(*idx))
+        {
+            Some(row) => {
+                self.select_absolute(row);
+                true
+            }
+            None => false,
+        }
+    }
+    pub fn select_next_bookmark(&mut self) {
+        let rows = self.bookmark_rows();
+        let curr = self.selected_row();
+        let next = rows.iter().find(|row| curr.is_none_or(|curr| **row > curr));
+        if let Some(row) = next.or(rows.first()) {
+            self.select_absolute(*row);
+        }
+    }
+    pub fn select_prev_bookmark(&mut self) {
+        let rows = self.bookmark_rows();
+        let curr = self.selected_row();
+        let prev = rows
+            .iter()
+            .rev()
+            .find(|row| curr.is_none_or(|curr| **row < curr));
+        if let Some(row) = prev.or(rows.last()) {
+            self.select_absolute(*row);
+        }
+    }
+    pub fn keymap(&self) -> &TableKeyMap {
+        &self.keymap
+    }
+    pub fn set_keymap(&mut self, keymap: &impl KeyMap<Command = TableCommand>) {
+        self.keymap = TableKeyMap(keymap.get_shortcuts().to_vec());
+        self.resolver.reset();
+    }
+    pub fn help(&self) -> Vec<HelpEntry> {
+        help_entries("Table", &self.keymap)
+    }
+    pub fn modes(&self) -> Option<Vec<Mode>> {
+        self.modes.clone()
+    }
+    pub fn set_modes(&mut self, modes: Option<Vec<Mode>>) {
+        self.modes = modes;
+    }
+    pub fn responds_in(&self, mode: &Mode) -> bool {
+        self.modes.as_ref().is_none_or(|modes| modes.contains(mode))
+    }
+    // a sequence, a count or a mark command is waiting for more keys
+    pub fn is_pending(&self) -> bool {
+        self.pending_mark.is_some()
+            || !self.resolver.pending().is_empty()
+            || self.resolver.count().is_some()
+    }
+    pub fn captures(&self, ev: &KeyEvent) -> bool {
+        self.is_pending() || self.keymap.get_command(ev).is_some() || self.keymap.is_prefix(&[*ev])
+    }
+    // a mark command waiting for its letter shows its own key
+    pub fn pending_keys(&self) -> String {
+        let Some(cmd) = &self.pending_mark else {
+            return self.resolver.pending_string();
+        };
+        self.keymap
+            .get_shortcuts()
+            .iter()
+            .find(|s| s.0 == *cmd)
+            .and_then(|s| s.1.first())
+            .map(|b| b.to_string())
+            .unwrap_or_default()
+    }
+    pub fn set_key_timeout(&mut self, timeout: Duration) {
+        self.resolver.set_timeout(timeout);
+    }
+    pub fn set_hint_delay(&mut self, delay: Option<Duration>) {
+        self.resolver.set_hint_delay(delay);
+    }
+    pub fn key_hints(&self) -> Option<KeyHints> {
+        self.resolver.hints(&self.keymap)
+    }
     pub fn rows_count(&self) -> usize {
         self.values.len()
     }
-    pub fn update(&mut self, ev: &Event) {
+    pub fn status(&self) -> &TableStatus {
+        &self.status
+    }
+    pub fn set_status(&mut self, status: TableStatus) {
+        self.status = status;
+        self.status_since = Instant::now();
+    }
+    pub fn set_empty_message(&mut self, msg: String) {
+        self.empty_message = msg;
+    }
+    pub fn set_hyperlinks(&mut self, enabled: bool) {
+        self.hyperlinks = enabled;
+    }
+    pub fn set_link_opener(&mut self, opener: Vec<String>) {
+        self.link_opener = opener;
+    }
+    pub fn selected_link(&self) -> Option<String> {
+        let data = &self.data[self.data_idx(self.selected_row()?)?];
+        match self.selected_col() {
+            Some(col) => data.cell_link(col),
+            None => (0..self.columns_count()).find_map(|col| data.cell_link(col)),
+        }
+    }
+    pub fn open_selected_link(&self) -> std::io::Result<()> {
+        match self.selected_link() {
+            Some(link) => hyperlink::open(&self.link_opener, &link),
+            None => Ok(()),
+        }
+    }
+    pub fn set_title(&mut self, title: Option<String>) {
+        self.title = title;
+        self.refresh_rows();
+    }
+    pub fn query(&self) -> Option<&Query> {
+        self.query.as_ref()
+    }
+    pub fn set_query(&mut self, query: &str) -> Result<(), QueryError> {
+        self.query = if query.trim().is_empty() {
+            None
+        } else {
+            let names = T::column_names().unwrap_or_default();
+            let kinds = T::column_kinds().unwrap_or_default();
+            Some(Query::parse(query, &names, &kinds)?)
+        };
+        self.refresh_rows();
+        Ok(())
+    }
+    pub fn clear_query(&mut self) {
+        self.query = None;
+        self.refresh_rows();
+    }
+    pub fn set_row_key(&mut self, key: impl Fn(&T) -> String + 'a) {
+        self.row_key = Some(Box::new(key));
+    }
+    pub fn set_change_fade(&mut self, fade: Duration) {
+        self.change_fade = fade;
+    }
+    pub fn set_follow(&mut self, follow: bool) {
+        self.follow = follow;
+        self.unseen = 0;
+        if follow {
+            self.select_bottom();
+        }
+        self.refresh_following();
+    }
+    pub fn is_following(&self) -> bool {
+        self.follow && self.following
+    }
+    pub fn unseen_rows(&self) -> usize {
+        self.unseen
+    }
+    // drops marks and bookmarks, `update_data` keeps them on their rows by `Tabular::value`
+    pub fn set_data(&mut self, data: Vec<T>) {
+        let old_len = self.data.len();
+        self.data = data;
+        self.marked.clear();
+        self.bookmarks.clear();
+        self.changes.clear();
+        self.ghosts.clear();
+        self.refresh_layout();
+        self.refresh_rows();
+        self.follow_tail(old_len);
+    }
+    pub fn update_data(&mut self, data: Vec<T>)
+    where
+        T::Value: Hash + Eq,
+    {
+        let now = Instant::now();
+        let old = std::mem::replace(&mut self.data, data);
+        let old_len = old.len();
+        let selected = self.selected_row().and_then(|row| self.data_idx(row));
+
+        // only rows that were shown leave a ghost, along with their group
+        let mut old_rows = vec![None; old.len()];
+        let mut group = None;
+        for (row, entry) in self.entries.iter().enumerate() {
+            match entry {
+                Entry::Row(idx) => old_rows[*idx] = Some((row, group.clone())),
+                Entry::Ghost(ghost) => self.ghosts[*ghost].at = row,
+                Entry::Group(g) => group = Some(self.groups[*g].key.clone()),
+            }
+        }
+        // rows sharing a key are matched in order, the one at the same index first
+        let mut by_key: HashMap<_, Vec<usize>> = HashMap::new();
+        for (old_idx, row) in old.iter().enumerate() {
+            by_key.entry(self.row_key(row)).or_default().push(old_idx);
+        }
+
+        let mut old_to_new = vec![None; old.len()];
+        let mut changes = HashMap::new();
+        for (new_idx, row) in self.data.iter().enumerate() {
+            let old_idx = by_key.get_mut(&self.row_key(row)).and_then(|candidates| {
+                let pos = candidates
+                    .iter()
+                    .position(|idx| *idx == new_idx)
+                    .unwrap_or(0);
+                (pos < candidates.len()).then(|| candidates.remove(pos))
+            });
+
+            let Some(old_idx) = old_idx else {
+                changes.insert(new_idx, (Change::Added, now));
+                continue;
+            };
+            old_to_new[old_idx] = Some(new_idx);
+
+            let (old_content, new_content) = (old[old_idx].content(), row.content());
+            let cols = (usize::from(self.indexed)..new_content.len())
+                .filter(|col| old_content.get(*col) != new_content.get(*col))
+                .collect_vec();
+            if !cols.is_empty() {
+                changes.insert(new_idx, (Change::Cells(cols), now));
+            } else if let Some(change) = self.changes.get(&old_idx) {
+                changes.insert(new_idx, change.clone());
+            }
+        }
+
+        let keys = self
+            .data
+            .iter()
+            .map(|row| self.row_key(row))
+            .collect::<HashSet<_>>();
+        let ghosts = std::mem::take(&mut self.ghosts);
+        self.ghosts = ghosts
+            .into_iter()
+            .filter(|ghost| !keys.contains(&self.row_key(&ghost.row)))
+            .collect();
+        for ((row, new_idx), shown) in old.into_iter().zip(&old_to_new).zip(old_rows) {
+            if let (None, Some((at, group))) = (new_idx, shown) {
+                self.ghosts.push(Ghost {
+                    row,
+                    at,
+                    group,
+                    since: now,
+                });
+            }
+        }
+
+        self.changes = changes;
+        self.marked = self
+            .marked
+            .iter()
+            .filter_map(|idx| old_to_new.get(*idx).copied().flatten())
+            .collect();
+        self.bookmarks = std::mem::take(&mut self.bookmarks)
+            .into_iter()
+            .filter_map(|(name, idx)| Some((name, old_to_new.get(idx).copied().flatten()?)))
+            .collect();
+        self.refresh_layout();
+        self.refresh_rows();
+
+        let selected = selected.and_then(|idx| old_to_new.get(idx).copied().flatten());
+        if let Some(row) =
+            selected.and_then(|idx| self.entries.iter().position(|e| *e == Entry::Row(idx)))
+        {
+            self.select_absolute(row);
+        }
+        self.follow_tail(old_len);
+    }
+    pub fn set_style_fn(&mut self, f: impl Fn(&T, usize) -> Option<Style> + 'a) {
+        self.style_fn = Some(Box::new(f));
+        self.refresh_rows();
+    }
+    pub fn update(&mut self, ev: &Event) -> Option<TableCommand> {
         match ev {
+            Event::Key(ev) if keyboard::is_release(ev) => {}
             Event::Key(ev) => {
-                if let Some(cmd) = self.keymap.get_command(ev) {
-                    match cmd {
-                        TableCommand::GoDown => self.select_next(),
-                        TableCommand::GoUp => self.select_prev(),
-                        TableCommand::GoDownCycle => {
-                            if let Some(idx) = self.selected_row() {
-                                if idx == self.rows_count() - 1 {
-                                    self.select_absolute(0);
-                                } else {
-                                    self.select_next();
-                                }
-                            }
-                        }
-                        TableCommand::GoUpCycle => {
-                            if let Some(idx) = self.selected_row() {
-                                if idx == 0 {
-                                    self.select_absolute(self.rows_count() - 1);
-                                } else {
-                                    self.select_prev();
-                                }
-                            }
-                        }
-                        TableCommand::GoPageDown => self.select_next_page(),
-                        TableCommand::GoPageUp => self.select_prev_page(),
-                        TableCommand::GoHalfPageDown => {
-                            let offset = self.rows_area().height as isize / 2;
-                            self.select_relative(offset);
-                        } // TableCommand::GoHalfPageUp => {
-                          //     let offset = self.rows_area().height as isize / 2;
-                          //     self.select_relative(-offset);
-                          // }
+                if let Some(pending) = self.pending_mark.take() {
+                    if let KeyCode::Char(c) = ev.code {
+                        self.handle_mark(&pending, c);
                     }
+                    return None;
                 }
+                return match self.resolver.resolve(&self.keymap, ev) {
+                    Resolution::Matched(cmd, count) => {
+                        self.handle_command_with_count(&cmd, count);
+                        Some(cmd)
+                    }
+                    Resolution::Pending | Resolution::NoMatch(_) => None,
+                };
             }
             Event::Mouse(ev) => {
                 let pos = Position {
                     x: ev.column,
                     y: ev.row,
                 };
-                if !self.area.contains(pos) {
-                    return;
+                // the loading and error messages cover the rows
+                if !self.area.contains(pos) || self.status != TableStatus::Ready {
+                    return None;
                 }
-                match ev.kind {
-                    MouseEventKind::ScrollDown => match ev.modifiers {
-                        KeyModifiers::ALT => self.select_relative(2),
-                        _ => self.select_next(),
-                    },
-                    MouseEventKind::ScrollUp => match ev.modifiers {
-                        KeyModifiers::ALT => self.select_relative(-2),
-                        _ => self.select_prev(),
-                    },
-                    MouseEventKind::Down(MouseButton::Left | MouseButton::Right) => {
-                        if let Some(row) = self.screen_coords_to_row_index(pos) {
-                            self.select_absolute(row);
-                        } else if let Some(col) = self.screen_coords_to_col_index(pos) {
-                            self.select_absolute_col(col);
-                            self.sort_rows();
+                let clicks = self.clicks.register(ev);
+                let cmd = self.keymap.get_mouse_command(ev, clicks)?.clone();
+                if let MouseEventKind::Down(_) = ev.kind {
+                    if let Some(row) = self.screen_coords_to_row_index(pos) {
+                        self.select_absolute(row);
+                    } else if let Some(col) = self.screen_coords_to_col_index(pos) {
+                        // headers only sort
+                        if cmd != TableCommand::Select {
+                            return None;
                         }
+                        self.select_absolute_col(col);
+                        self.sort_by_col(col);
+                    } else {
+                        return None;
                     }
-                    _ => {}
                 }
+                self.handle_command(&cmd);
+                return Some(cmd);
             }
             _ => {}
         }
+        None
+    }
+    pub fn handle_command(&mut self, cmd: &TableCommand) {
+        self.handle_command_with_count(cmd, None);
+    }
+    // motions move `count` times as far, a count on `GoTop`/`GoBottom` is a row number
+    pub fn handle_command_with_count(&mut self, cmd: &TableCommand, count: Option<usize>) {
+        if self.status != TableStatus::Ready {
+            return;
+        }
+        let n = count.unwrap_or(1).max(1);
+        let offset = isize::try_from(n).unwrap_or(isize::MAX);
+        match cmd {
+            TableCommand::GoDown => self.select_relative(offset),
+            TableCommand::GoUp => self.select_relative(-offset),
+            TableCommand::GoDownFast => self.select_relative(offset.saturating_mul(2)),
+            TableCommand::GoUpFast => self.select_relative(-offset.saturating_mul(2)),
+            TableCommand::GoDownCycle => {
+                for _ in 0..n {
+                    if let Some(idx) = self.selected_row() {
+                        if idx + 1 >= self.rows_count() {
+                            self.select_top();
+                        } else {
+                            self.select_next();
+                        }
+                    }
+                }
+            }
+            TableCommand::GoUpCycle => {
+                for _ in 0..n {
+                    if let Some(idx) = self.selected_row() {
+                        if idx == 0 {
+                            self.select_bottom();
+                        } else {
+                            self.select_prev();
+                        }
+                    }
+                }
+            }
+            TableCommand::GoPageDown => {
+                let page = self.rows_area().height as isize;
+                self.select_relative(page.saturating_mul(offset));
+            }
+            TableCommand::GoPageUp => {
+                let page = self.rows_area().height as isize;
+                self.select_relative(-page.saturating_mul(offset));
+            }
+            TableCommand::GoHalfPageDown => {
+                let half = self.rows_area().height as isize / 2;
+                self.select_relative(half.saturating_mul(offset));
+            }
+            TableCommand::GoHalfPageUp => {
+                let half = self.rows_area().height as isize / 2;
+                self.select_relative(-half.saturating_mul(offset));
+            }
+            TableCommand::GoTop | TableCommand::GoBottom if count.is_some() => {
+                self.select_nth_row(n)
+            }
+            TableCommand::GoTop => self.select_top(),
+            TableCommand::GoBottom => self.select_bottom(),
+            TableCommand::GoLeft => {
+                if self.cell_mode {
+                    self.select_relative_col(-offset);
+                }
+            }
+            TableCommand::GoRight => {
+                if self.cell_mode {
+                    self.select_relative_col(offset);
+                }
+            }
+            TableCommand::ToggleCellMode => self.toggle_cell_mode(),
+            // a count marks as many rows downwards
+            TableCommand::ToggleMark => {
+                for i in 0..n {
+                    if i > 0 {
+                        self.select_next();
+                    }
+                    self.toggle_mark();
+                }
+            }
+            TableCommand::YankCell => {
+                if let Some(content) = self.selected_cell_content() {
+                    let _ = clipboard::copy(&content);
+                }
+            }
+            TableCommand::YankRow => {
+                if let Some(content) = self.selected_row_content() {
+                    let _ = clipboard::copy(&content);
+                }
+            }
+            TableCommand::YankMarked => {
+                if let Some(content) = self.marked_rows_content() {
+                    let _ = clipboard::copy(&content);
+                }
+            }
+            // the prompt is owned by the app, see `set_query`
+            TableCommand::Filter => {}
+            TableCommand::ToggleGroup => self.toggle_group(),
+            TableCommand::OpenLink => {
+                let _ = self.open_selected_link();
+            }
+            // reloading is owned by the app
+            TableCommand::Retry => {}
+            // the popup is owned by the app, see `column_stats`
+            TableCommand::ColumnStats => {}
+            // the popup is owned by the app, see `help`
+            TableCommand::Help => {}
+            // the row under the pointer is selected before any mouse command runs
+            TableCommand::Select => {}
+            // the mark name is the next key, see `update`
+            TableCommand::SetMark | TableCommand::JumpToMark => {
+                self.pending_mark = Some(cmd.clone())
+            }
+            TableCommand::NextMark => (0..n).for_each(|_| self.select_next_bookmark()),
+            TableCommand::PrevMark => (0..n).for_each(|_| self.select_prev_bookmark()),
+        }
+        self.refresh_following();
+    }
+    // runs a sequence that timed out or keys queued behind a match, call it
+    // with every iteration of the event loop
+    pub fn tick(&mut self) -> Option<TableCommand> {
+        match self.resolver.tick(&self.keymap)? {
+            Resolution::Matched(cmd, count) => {
+                self.handle_command_with_count(&cmd, count);
+                Some(cmd)
+            }
+            Resolution::Pending | Resolution::NoMatch(_) => None,
+        }
     }
     pub fn draw(&mut self, f: &mut Frame<'_>, area: Rect) {
+        self.expire_changes();
         self.area = area;
         f.render_stateful_widget(&self.table, area, &mut self.state);
+        self.render_cells(f.buffer_mut(), &self.state);
+        self.render_status(f.buffer_mut());
     }
     pub fn state(&self) -> &TableState {
         &self.state
     }
     pub fn min_area(&self) -> (u16, u16) {
-        let w = self.inner_width + self.padding.l + self.padding.r;
-        let h = (self.rows_count() as u16 * T::row_height()) + self.padding.t + self.padding.b;
+        let rows = u16::try_from(self.rows_count()).unwrap_or(u16::MAX);
+        let w = self
+            .inner_width
+            .saturating_add(self.padding.l)
+            .saturating_add(self.padding.r);
+        let h = rows
+            .saturating_mul(T::row_height())
+            .saturating_add(self.padding.t)
+            .saturating_add(self.padding.b);
         (w, h)
     }
     pub fn header_area(&self) -> Option<Rect> {
         T::column_names()?;
         let area = self.rows_area();
+        if area.y <= self.area.y {
+            return None;
+        }
         Some(Rect {
             x: area.x,
             y: area.y - 1,
@@ -215,11 +1004,13 @@ impl<'a, T: Tabular> StatefulTable<'a, T> {
         })
     }
     pub fn rows_area(&self) -> Rect {
+        let l = self.padding.l.min(self.area.width);
+        let t = self.padding.t.min(self.area.height);
         Rect {
-            x: self.area.x + self.padding.l,
-            y: self.area.y + self.padding.t,
-            width: self.area.width - self.padding.l - self.padding.r,
-            height: self.area.height - self.padding.t - self.padding.b,
+            x: self.area.x.saturating_add(l),
+            y: self.area.y.saturating_add(t),
+            width: (self.area.width - l).saturating_sub(self.padding.r),
+            height: (self.area.height - t).saturating_sub(self.padding.b),
         }
     }
     pub fn screen_coords_to_row_index(&self, pos: Position) -> Option<usize> {
@@ -260,6 +1051,13 @@ impl<'a, T: Tabular> StatefulTable<'a, T> {
     pub fn select_prev_page(&mut self) {
         self.select_relative(-(self.rows_area().height as isize))
     }
+    pub fn select_top(&mut self) {
+        self.select_absolute(self.skip_group_headers(0, false));
+    }
+    pub fn select_bottom(&mut self) {
+        let bottom = self.rows_count().saturating_sub(1);
+        self.select_absolute(self.skip_group_headers(bottom, true));
+    }
     pub fn select_absolute(&mut self, idx: usize) {
         let idx = idx.clamp(0, self.rows_count().saturating_sub(1));
         self.state.select(Some(idx));
@@ -275,7 +1073,8 @@ impl<'a, T: Tabular> StatefulTable<'a, T> {
                 curr.saturating_add(offset.unsigned_abs())
             }
         });
-        self.select_absolute(new);
+        let new = new.clamp(0, self.rows_count().saturating_sub(1));
+        self.select_absolute(self.skip_group_headers(new, offset < 0));
     }
     pub fn select_next_col(&mut self) {
         self.select_relative_col(1);
@@ -294,8 +1093,18 @@ impl<'a, T: Tabular> StatefulTable<'a, T> {
         self.select_absolute_col(new);
     }
     pub fn select_absolute_col(&mut self, idx: usize) {
-        let idx = idx.clamp(0, self.col_constraints.len().saturating_sub(1));
-        match self.selected_col() {
+        // the index column is never selected
+        let idx = idx
+            .max(usize::from(self.indexed))
+            .min(self.columns_count().saturating_sub(1));
+        self.state.select_column(Some(idx));
+    }
+    pub fn sort_by_col(&mut self, idx: usize) {
+        let idx = idx.clamp(0, self.columns_count().saturating_sub(1));
+        if self.indexed && idx == 0 {
+            return;
+        }
+        match self.sort_col {
             Some(old) if old != idx => self.selected_col_ord = Ordering::Equal,
             Some(_) => {
                 self.selected_col_ord = match self.selected_col_ord {
@@ -306,44 +1115,32 @@ impl<'a, T: Tabular> StatefulTable<'a, T> {
             }
             None => self.selected_col_ord = Ordering::Equal,
         }
-        self.state.select_column(Some(idx));
+        self.sort_col = Some(idx);
+        self.refresh_rows();
+    }
+    pub fn columns_count(&self) -> usize {
+        T::column_constraints().len()
     }
 
     // --- helpers
     fn build_table(
         data: Vec<T>,
         mut state: TableState,
-        mut style: TableStyle<'a>,
+        style: TableStyle<'a>,
         title: Option<String>,
         indexed: bool,
     ) -> Self {
-        let values = data.iter().map(T::value).collect();
         if let Some(idx) = state.selected() {
             state.select(Some(idx.clamp(0, data.len().saturating_sub(1))));
         }
 
-        let col_widths = Self::columns_max_widths(&data);
-        let constraints = col_widths
-            .iter()
-            .zip(T::column_constraints().iter())
-            .map(|(s, c)| c(*s))
-            .collect_vec();
-
-        let col_constraints = constraints
-            .clone()
-            .into_iter()
-            .interleave(vec![
-                Constraint::Length(style.column_spacing);
-                constraints.len() - 1
-            ])
-            .collect();
-
         let alignments = Self::alignemnts();
-        let mut table = Table::new(Self::build_rows(&data, &alignments), constraints)
+        let mut table = Table::default()
             .style(style.normal)
             .column_spacing(style.column_spacing)
             .row_highlight_style(style.highlight)
-            .column_highlight_style(style.col_highlight);
+            .column_highlight_style(style.col_highlight)
+            .cell_highlight_style(style.col_highlight);
 
         let mut padding = Padding::default();
         if let Some(header) = Self::build_header(&alignments, None, Ordering::Equal, style.header) {
@@ -352,52 +1149,204 @@ impl<'a, T: Tabular> StatefulTable<'a, T> {
         }
 
         padding.add_padding(style.block.1);
-        if let Some(title) = &title {
-            style.block.0 = style.block.0.title(title.clone());
-        }
-        table = table.block(style.block.0.clone());
-        let inner_width =
-            col_widths.iter().sum::<u16>() + (style.column_spacing * (col_widths.len() - 1) as u16);
 
-        Self {
+        let mut table = Self {
             table,
             state,
             style,
             padding,
-            inner_width,
-            values,
+            inner_width: 0,
+            values: vec![],
+            positions: vec![],
             data,
-            col_constraints,
+            col_constraints: vec![],
             area: Rect::default(),
             keymap: KeyMap::default(),
+            sort_col: None,
             selected_col_ord: Ordering::Equal,
+            entries: vec![],
             indexed,
+            style_fn: None,
+            cell_mode: false,
+            marked: HashSet::new(),
+            title,
+            query: None,
+            row_key: None,
+            changes: HashMap::new(),
+            ghosts: vec![],
+            change_fade: Duration::from_secs(2),
+            group_by: None,
+            groups: vec![],
+            collapsed: HashSet::new(),
+            aggregate_fn: None,
+            skip_group_headers: false,
+            hyperlinks: hyperlink::is_supported(),
+            link_opener: hyperlink::default_opener(),
+            status: TableStatus::Ready,
+            status_since: Instant::now(),
+            empty_message: "No data".into(),
+            follow: false,
+            following: false,
+            unseen: 0,
+            bookmarks: BTreeMap::new(),
+            pending_mark: None,
+            resolver: KeyResolver::default(),
+            clicks: ClickCounter::default(),
+            modes: None,
+        };
+        table.refresh_layout();
+        table.refresh_rows();
+        table
+    }
+    fn refresh_layout(&mut self) {
+        let spacing = self.style.column_spacing;
+        let mut col_widths = Self::columns_max_widths(&self.data);
+        if self.indexed && !self.bookmarks.is_empty() {
+            // room for the bookmark name in the index column
+            col_widths[0] += 2;
         }
+        let constraints = col_widths
+            .iter()
+            .zip(T::column_constraints().iter())
+            .map(|(s, c)| c(*s))
+            .collect_vec();
+
+        self.col_constraints = constraints
+            .clone()
+            .into_iter()
+            .interleave(vec![
+                Constraint::Length(spacing);
+                constraints.len().saturating_sub(1)
+            ])
+            .collect();
+        self.inner_width =
+            col_widths.iter().sum::<u16>() + (spacing * col_widths.len().saturating_sub(1) as u16);
+        self.table = std::mem::take(&mut self.table).widths(constraints);
     }
     fn columns_max_widths(data: &[T]) -> Vec<u16> {
-        let widths = |a: Vec<String>| a.iter().map(|e| e.len() as u16).collect();
-        let header_widths = |a: Vec<String>| a.iter().map(|e| 1 + e.len() as u16).collect();
+        let width = |e: &String| Span::raw(e.as_str()).width() as u16;
+        let widths = |a: Vec<String>| a.iter().map(width).collect();
+        let header_widths = |a: Vec<String>| a.iter().map(|e| 1 + width(e)).collect();
         let max_widths = |a: Vec<u16>, b: Vec<u16>| (0..a.len()).map(|i| a[i].max(b[i])).collect();
         let mut row_widths = data.iter().map(T::content).map(widths).collect_vec();
         if let Some(header_widths) = T::column_names().map(header_widths) {
             row_widths.push(header_widths);
         }
-        row_widths.into_iter().reduce(max_widths).unwrap()
-    }
-    fn build_rows(data: &[impl Tabular], alignments: &[Alignment]) -> Vec<Row<'a>> {
-        data.iter()
-            .map(|row| {
-                Row::new(
-                    row.content()
-                        .into_iter()
-                        .zip(alignments)
-                        .map(|(c, a)| Text::raw(c).alignment(*a)),
-                )
-                .style(row.style())
-                .height(T::row_height())
+        row_widths
+            .into_iter()
+            .reduce(max_widths)
+            .unwrap_or_else(|| vec![0; T::column_constraints().len()])
+    }
+    fn build_rows(&self) -> Vec<Row<'a>> {
+        let alignments = Self::alignemnts();
+        self.entries
+            .iter()
+            .zip(&self.positions)
+            .map(|(entry, pos)| match entry {
+                Entry::Group(group) => self.build_group_row(&self.groups[*group], &alignments),
+                Entry::Row(idx) => self.build_row(*pos, *idx, &alignments),
+                Entry::Ghost(ghost) => self.build_ghost_row(&self.ghosts[*ghost].row, &alignments),
             })
             .collect()
     }
+    fn build_ghost_row(&self, ghost: &T, alignments: &[Alignment]) -> Row<'a> {
+        let mut content = ghost.content();
+        if self.indexed {
+            content[0] = "-".into();
+        }
+        Row::new(
+            content
+                .into_iter()
+                .zip(alignments)
+                .map(|(c, a)| Text::raw(c).alignment(*a)),
+        )
+        .style(self.style.removed)
+        .height(T::row_height())
+    }
+    fn build_group_row(&self, group: &Group, alignments: &[Alignment]) -> Row<'a> {
+        let symbol = if group.collapsed {
+            Self::GROUP_COLLAPSED
+        } else {
+            Self::GROUP_EXPANDED
+        };
+        Row::new(alignments.iter().enumerate().map(|(col, a)| {
+            if col == group.col {
+                Text::raw(format!("{symbol} {} ({})", group.key, group.count))
+            } else {
+                let aggregate = group.aggregates.get(col).cloned().flatten();
+                Text::raw(aggregate.unwrap_or_default()).alignment(*a)
+            }
+        }))
+        .style(self.style.group)
+        .height(T::row_height())
+    }
+    fn build_row(&self, pos: usize, idx: usize, alignments: &[Alignment]) -> Row<'a> {
+        let mut content = self.data[idx].content();
+        if self.indexed {
+            // indexes follow the displayed order
+            content[0] = match self.bookmarks.iter().find(|(_, i)| **i == idx) {
+                Some((name, _)) => format!("{name} {pos}"),
+                None => format!("{pos}"),
+            };
+        }
+
+        let row_style = self.row_style(pos, idx, &content);
+        let cell_styles = (0..content.len())
+            .map(|col| self.cell_style(idx, &content, col))
+            .collect_vec();
+
+        Row::new(
+            content
+                .into_iter()
+                .zip(alignments)
+                .zip(cell_styles)
+                .map(|((c, a), style)| Cell::from(Text::raw(c).alignment(*a)).style(style)),
+        )
+        .style(row_style)
+        .height(T::row_height())
+    }
+    // zebra, the row's own style, row rules, marks and additions
+    fn row_style(&self, pos: usize, idx: usize, content: &[String]) -> Style {
+        let mut style = if pos % 2 == 1 {
+            self.style.zebra
+        } else {
+            Style::default()
+        }
+        .patch(self.data[idx].style());
+        for rule in self.rules(RuleScope::Row) {
+            if rule.matches_row(content) {
+                style = style.patch(rule.style);
+            }
+        }
+        if self.marked.contains(&idx) {
+            style = style.patch(self.style.marked);
+        }
+        if let Some((Change::Added, _)) = self.changes.get(&idx) {
+            style = style.patch(self.style.added);
+        }
+        style
+    }
+    // cell rules, the style function and changed cells, on top of the row style
+    fn cell_style(&self, idx: usize, content: &[String], col: usize) -> Style {
+        let mut style = Style::default();
+        for rule in self.rules(RuleScope::Cell) {
+            if rule.matches_cell(content, col) {
+                style = style.patch(rule.style);
+            }
+        }
+        if let Some(s) = self.style_fn.as_ref().and_then(|f| f(&self.data[idx], col)) {
+            style = style.patch(s);
+        }
+        if let Some((Change::Cells(cols), _)) = self.changes.get(&idx) {
+            if cols.contains(&col) {
+                style = style.patch(self.style.changed);
+            }
+        }
+        style
+    }
+    fn rules(&self, scope: RuleScope) -> impl Iterator<Item = &StyleRule> {
+        self.style.rules.iter().filter(move |r| r.scope == scope)
+    }
     fn alignemnts() -> Vec<Alignment> {
         T::column_alignments().unwrap_or(vec![Alignment::default(); T::column_constraints().len()])
     }
@@ -428,41 +1377,389 @@ impl<'a, T: Tabular> StatefulTable<'a, T> {
             .style(header_style)
         })
     }
-    fn sort_rows(&mut self) {
-        if let Some(col) = self.selected_col() {
-            if self.indexed && col == 0 {
-                return;
-            }
-            let mut data = self.data.clone();
+    fn filtered_rows(&self) -> Vec<usize> {
+        (0..self.data.len())
+            .filter(|idx| {
+                self.query
+                    .as_ref()
+                    .is_none_or(|q| q.matches(&self.data[*idx].content()))
+            })
+            .collect()
+    }
+    fn refresh_rows(&mut self) {
+        let mut order = self.filtered_rows();
+        let data = &self.data;
+        if let Some(col) = self.sort_col {
             match self.selected_col_ord {
-                Ordering::Less => data.sort_by(|a, b| a.cmp_by_col(b, col)),
-                Ordering::Greater => data.sort_by(|a, b| b.cmp_by_col(a, col)),
+                Ordering::Less => order.sort_by(|a, b| data[*a].cmp_by_col(&data[*b], col)),
+                Ordering::Greater => order.sort_by(|a, b| data[*b].cmp_by_col(&data[*a], col)),
                 Ordering::Equal => {}
             }
+        }
 
-            let alignments = Self::alignemnts();
-            let rows = if self.indexed {
-                // rebuild indexes
-                Self::build_rows(
-                    &IndexedRow::from(data.iter().map(T::data).collect()),
-                    &alignments,
-                )
-            } else {
-                Self::build_rows(&data, &alignments)
+        self.groups.clear();
+        self.entries = match self.group_by {
+            None => order.into_iter().map(Entry::Row).collect(),
+            Some(group_by) => {
+                let mut entries = vec![];
+                for (key, rows) in Self::group_rows(data, order, group_by) {
+                    let collapsed = self.collapsed.contains(&key);
+                    let aggregates = self.aggregate_fn.as_ref().map_or(vec![], |f| {
+                        let rows = rows.iter().map(|idx| &data[*idx]).collect_vec();
+                        (0..T::column_constraints().len())
+                            .map(|col| f(&rows, col))
+                            .collect()
+                    });
+                    entries.push(Entry::Group(self.groups.len()));
+                    self.groups.push(Group {
+                        key,
+                        col: group_by.col(),
+                        count: rows.len(),
+                        collapsed,
+                        aggregates,
+                    });
+                    if !collapsed {
+                        entries.extend(rows.into_iter().map(Entry::Row));
+                    }
+                }
+                entries
+            }
+        };
+        // ghosts go the way of the rows around them, filtered out or collapsed
+        let (query, groups) = (&self.query, &self.groups);
+        self.ghosts.retain(|ghost| {
+            query
+                .as_ref()
+                .is_none_or(|q| q.matches(&ghost.row.content()))
+                && (self.group_by.is_none()
+                    || groups
+                        .iter()
+                        .any(|g| Some(&g.key) == ghost.group.as_ref() && !g.collapsed))
+        });
+        let ghosts = (0..self.ghosts.len()).sorted_by_key(|ghost| self.ghosts[*ghost].at);
+        for ghost in ghosts {
+            let Ghost { at, group, .. } = &self.ghosts[ghost];
+            let start = group.as_ref().map_or(0, |key| {
+                self.entries
+                    .iter()
+                    .position(|e| matches!(e, Entry::Group(g) if self.groups[*g].key == *key))
+                    .map_or(0, |header| header + 1)
+            });
+            let end = self.entries[start..]
+                .iter()
+                .position(|e| matches!(e, Entry::Group(_)))
+                .map_or(self.entries.len(), |len| start + len);
+            self.entries
+                .insert((*at).clamp(start, end), Entry::Ghost(ghost));
+        }
+        self.values = self
+            .entries
+            .iter()
+            .map(|entry| match entry {
+                Entry::Row(idx) => Some(data[*idx].value()),
+                Entry::Group(_) | Entry::Ghost(_) => None,
+            })
+            .collect();
+        self.positions = self
+            .entries
+            .iter()
+            .scan(0, |pos, entry| {
+                let current = *pos;
+                *pos += usize::from(matches!(entry, Entry::Row(_)));
+                Some(current)
+            })
+            .collect();
+
+        let alignments = Self::alignemnts();
+        let rows = self.build_rows();
+
+        let mut table = std::mem::take(&mut self.table);
+        table = table.rows(rows);
+        if let Some(header) = Self::build_header(
+            &alignments,
+            self.sort_col,
+            self.selected_col_ord,
+            self.style.header,
+        ) {
+            table = table.header(header);
+        }
+        self.table = table.block(self.build_block());
+
+        if let Some(row) = self.selected_row() {
+            self.select_absolute(row);
+        }
+    }
+    fn build_block(&self) -> Block<'a> {
+        let mut title = self.title.iter().cloned().collect_vec();
+        if let Some(query) = &self.query {
+            title.push(format!("[{query}]"));
+        }
+        if self.follow {
+            title.push(match (self.following, self.unseen) {
+                (true, _) => "[following]".into(),
+                (false, 0) => "[paused]".into(),
+                (false, 1) => "[paused, 1 new row]".into(),
+                (false, n) => format!("[paused, {n} new rows]"),
+            });
+        }
+        if title.is_empty() {
+            self.style.block.0.clone()
+        } else {
+            self.style.block.0.clone().title(title.join(" "))
+        }
+    }
+    fn follow_tail(&mut self, old_len: usize) {
+        if !self.follow {
+            return;
+        }
+        if self.following {
+            self.select_bottom();
+        } else {
+            self.unseen += self.data.len().saturating_sub(old_len);
+        }
+        self.refresh_following();
+    }
+    fn refresh_following(&mut self) {
+        if !self.follow {
+            return;
+        }
+        let bottom = self
+            .rows_count()
+            .checked_sub(1)
+            .map(|row| self.skip_group_headers(row, true));
+        self.following = bottom.is_none() || self.selected_row() == bottom;
+        if self.following {
+            self.unseen = 0;
+        }
+        self.table = std::mem::take(&mut self.table).block(self.build_block());
+    }
+    fn expire_changes(&mut self) {
+        let fade = self.change_fade;
+        let (changes, ghosts) = (self.changes.len(), self.ghosts.len());
+        let selected = self.selected_row().and_then(|row| self.data_idx(row));
+        self.changes.retain(|_, (_, at)| at.elapsed() < fade);
+        self.ghosts.retain(|ghost| ghost.since.elapsed() < fade);
+        if changes == self.changes.len() && ghosts == self.ghosts.len() {
+            return;
+        }
+        self.refresh_rows();
+        // rows below an expired ghost move up, the selection moves with them
+        if let Some(row) = selected.and_then(|idx| self.bookmark_row(idx)) {
+            self.select_absolute(row);
+        }
+    }
+    fn row_key(&self, row: &T) -> RowKey<T::Value> {
+        match &self.row_key {
+            Some(key) => RowKey::Custom(key(row)),
+            None => RowKey::Value(row.value()),
+        }
+    }
+    fn group_rows(
+        data: &[T],
+        mut order: Vec<usize>,
+        group_by: GroupBy,
+    ) -> Vec<(String, Vec<usize>)> {
+        let col = group_by.col();
+        let key = |idx: usize| data[idx].content().get(col).cloned().unwrap_or_default();
+        let mut groups: Vec<(String, Vec<usize>)> = vec![];
+        match group_by {
+            GroupBy::Key(_) => {
+                let mut positions: HashMap<String, usize> = HashMap::new();
+                for idx in order {
+                    let key = key(idx);
+                    match positions.get(&key) {
+                        Some(pos) => groups[*pos].1.push(idx),
+                        None => {
+                            positions.insert(key.clone(), groups.len());
+                            groups.push((key, vec![idx]));
+                        }
+                    }
+                }
+            }
+            GroupBy::Cmp(_) => {
+                // stable, so rows keep the sorting order within each group
+                order.sort_by(|a, b| data[*a].cmp_by_col(&data[*b], col));
+                for idx in order {
+                    match groups.last_mut() {
+                        Some((_, rows)) if data[rows[0]].cmp_by_col(&data[idx], col).is_eq() => {
+                            rows.push(idx)
+                        }
+                        _ => groups.push((key(idx), vec![idx])),
+                    }
+                }
+            }
+        }
+        groups
+    }
+    fn render_cells(&self, buf: &mut Buffer, state: &TableState) {
+        let area = self.rows_area().intersection(buf.area);
+        let columns = Layout::horizontal(self.col_constraints.iter().step_by(2).copied())
+            .spacing(self.style.column_spacing)
+            .split(area);
+        let alignments = Self::alignemnts();
+        let visible = (area.height / T::row_height().max(1)) as usize;
+
+        for (i, row) in (state.offset()..self.entries.len())
+            .take(visible)
+            .enumerate()
+        {
+            let Some(idx) = self.data_idx(row) else {
+                continue;
             };
+            let data = &self.data[idx];
+            let selected = state.selected() == Some(row);
+            let y = area.y + i as u16 * T::row_height();
+            // only cells drawn over the table need their style, built once per row
+            let mut row_style = None;
+            for (col, rect) in columns.iter().enumerate() {
+                let cell = Rect {
+                    y,
+                    height: T::row_height(),
+                    ..*rect
+                }
+                .intersection(area);
+                let Some(renderer) = data.cell_renderer(col) else {
+                    if let Some(link) = data.cell_link(col).filter(|_| self.hyperlinks) {
+                        hyperlink::render(cell, buf, &link);
+                    }
+                    continue;
+                };
+                let (content, style) = row_style.get_or_insert_with(|| {
+                    let content = data.content();
+                    let pos = self.positions[row];
+                    let style = self.style.normal.patch(self.row_style(pos, idx, &content));
+                    (content, style)
+                });
+                // the layering of `Table`: row, cell, then row, column and cell highlights
+                let mut style = style.patch(self.cell_style(idx, content, col));
+                let col_selected = state.selected_column() == Some(col);
+                if selected {
+                    style = style.patch(self.style.highlight);
+                }
+                if col_selected {
+                    style = style.patch(self.col_highlight());
+                }
+                if selected && col_selected {
+                    style = style.patch(self.cell_highlight());
+                }
+                let alignment = alignments.get(col).copied().unwrap_or_default();
+                renderer.render(cell, buf, style, alignment);
+            }
+        }
+    }
+    fn render_status(&self, buf: &mut Buffer) {
+        let (msg, style) = match &self.status {
+            TableStatus::Loading => {
+                let frame = self.status_since.elapsed().as_millis()
+                    / Self::SPINNER_FRAME.as_millis()
+                    % Self::SPINNER.len() as u128;
+                let spinner = Self::SPINNER[frame as usize];
+                (format!("{spinner} Loading..."), self.style.loading)
+            }
+            TableStatus::Error(err) => {
+                let keys = self
+                    .keymap
+                    .get_shortcuts()
+                    .iter()
+                    .find(|s| matches!(s.0, TableCommand::Retry))
+                    .map(|s| s.content().remove(1));
+                let msg = match keys {
+                    Some(keys) => format!("{err}\npress {keys} to retry"),
+                    None => err.clone(),
+                };
+                (msg, self.style.error)
+            }
+            TableStatus::Ready if self.rows_count() == 0 => {
+                (self.empty_message.clone(), self.style.empty)
+            }
+            TableStatus::Ready => return,
+        };
 
-            let mut table = std::mem::take(&mut self.table);
-            table = table.rows(rows);
-            if let Some(header) = Self::build_header(
-                &alignments,
-                self.selected_col(),
-                self.selected_col_ord,
-                self.style.header,
-            ) {
-                table = table.header(header);
+        let area = self.rows_area().intersection(buf.area);
+        let text = Text::from(msg).style(style).alignment(Alignment::Center);
+        let height = (text.height() as u16).min(area.height);
+        let [area] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(area);
+        Clear.render(self.rows_area().intersection(buf.area), buf);
+        text.render(area, buf);
+    }
+    fn handle_mark(&mut self, cmd: &TableCommand, c: char) {
+        match cmd {
+            TableCommand::SetMark if c.is_ascii_lowercase() => self.set_bookmark(c),
+            TableCommand::JumpToMark if c.is_ascii_lowercase() => {
+                self.jump_to_bookmark(c);
             }
-            self.table = table;
-            self.values = data.iter().map(|d| d.value()).collect();
+            _ => {}
+        }
+        self.refresh_following();
+    }
+    fn bookmark_row(&self, idx: usize) -> Option<usize> {
+        self.entries.iter().position(|e| *e == Entry::Row(idx))
+    }
+    fn bookmark_rows(&self) -> Vec<usize> {
+        let rows = self
+            .bookmarks
+            .values()
+            .filter_map(|idx| self.bookmark_row(*idx));
+        rows.sorted().dedup().collect()
+    }
+    // counts data rows from 1, group headers and removed rows are left out
+    fn select_nth_row(&mut self, n: usize) {
+        let rows = (0..self.entries.len()).filter(|row| self.data_idx(*row).is_some());
+        if let Some(row) = rows.take(n).last() {
+            self.select_absolute(row);
+        }
+    }
+    fn data_idx(&self, row: usize) -> Option<usize> {
+        match self.entries.get(row) {
+            Some(Entry::Row(idx)) => Some(*idx),
+            _ => None,
+        }
+    }
+    fn selected_content(&self) -> Option<Vec<String>> {
+        let idx = self.data_idx(self.selected_row()?)?;
+        Some(self.data[idx].content())
+    }
+    fn marked_rows(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.entries.len()).filter(|row| {
+            self.data_idx(*row)
+                .is_some_and(|idx| self.marked.contains(&idx))
+        })
+    }
+    // ghosts are always skipped, group headers when `skip_group_headers` is set
+    fn skip_group_headers(&self, row: usize, backwards: bool) -> usize {
+        let skip = match self.entries.get(row) {
+            Some(Entry::Ghost(_)) => true,
+            Some(Entry::Group(_)) => self.skip_group_headers,
+            _ => false,
+        };
+        if !skip {
+            return row;
+        }
+        let is_row = |r: &usize| self.data_idx(*r).is_some();
+        let (before, after) = (
+            (0..row).rev().find(is_row),
+            (row..self.entries.len()).find(is_row),
+        );
+        if backwards {
+            before.or(after).unwrap_or(row)
+        } else {
+            after.or(before).unwrap_or(row)
+        }
+    }
+    fn col_highlight(&self) -> Style {
+        if self.cell_mode {
+            Style::default()
+        } else {
+            self.style.col_highlight
+        }
+    }
+    fn cell_highlight(&self) -> Style {
+        if self.cell_mode {
+            self.style.col_highlight
+        } else {
+            Style::default()
         }
     }
 }
@@ -470,7 +1767,26 @@ impl<T: Tabular> StatefulWidget for StatefulTable<'_, T> {
     type State = TableState;
     fn render(mut self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
         self.area = area;
-        self.table.render(area, buf, state);
+        StatefulWidget::render(&self.table, area, buf, state);
+        self.render_cells(buf, state);
+        self.render_status(buf);
+    }
+}
+impl<T: Tabular> KeyHandler for StatefulTable<'_, T> {
+    fn captures(&self, ev: &KeyEvent) -> bool {
+        self.captures(ev)
+    }
+    fn modes(&self) -> Option<Vec<Mode>> {
+        self.modes()
+    }
+    fn help(&self) -> Vec<HelpEntry> {
+        self.help()
+    }
+    fn key_hints(&self) -> Option<KeyHints> {
+        self.key_hints()
+    }
+    fn pending_keys(&self) -> String {
+        self.pending_keys()
     }
 }
 
@@ -487,6 +1803,17 @@ impl<T: Tabular> IndexedRow<T> {
         rows.sort_by(|a, b| cmp(&a.data, &b.data));
     }
 }
+impl<'a, T: Tabular> StatefulTable<'a, IndexedRow<T>> {
+    pub fn set_indexed_data(&mut self, data: Vec<T>) {
+        self.set_data(IndexedRow::from(data));
+    }
+    pub fn update_indexed_data(&mut self, data: Vec<T>)
+    where
+        T::Value: Hash + Eq,
+    {
+        self.update_data(IndexedRow::from(data));
+    }
+}
 impl<T: Tabular> IndexedRow<T> {
     fn from(data: Vec<T>) -> Vec<IndexedRow<T>> {
         data.into_iter()
@@ -522,6 +1849,26 @@ impl<T: Tabular> Tabular for IndexedRow<T> {
     fn style(&self) -> Style {
         T::style(&self.data)
     }
+    fn column_kinds() -> Option<Vec<ColumnKind>> {
+        T::column_kinds().map(|mut kinds| {
+            kinds.insert(0, ColumnKind::Number);
+            kinds
+        })
+    }
+    fn cell_renderer(&self, col: usize) -> Option<CellRenderer> {
+        if col == 0 {
+            None
+        } else {
+            self.data.cell_renderer(col - 1)
+        }
+    }
+    fn cell_link(&self, col: usize) -> Option<String> {
+        if col == 0 {
+            None
+        } else {
+            self.data.cell_link(col - 1)
+        }
+    }
     fn column_alignments() -> Option<Vec<Alignment>> {
         T::column_alignments().map(|mut alignemnts| {
             alignemnts.insert(0, Alignment::Center);
@@ -543,81 +1890,484 @@ impl<T: Tabular> Tabular for IndexedRow<T> {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TableCommand {
     GoDown,
     GoUp,
+    GoDownFast,
+    GoUpFast,
     GoDownCycle,
     GoUpCycle,
     GoPageDown,
     GoPageUp,
     GoHalfPageDown,
-    // GoHalfPageUp,
+    GoHalfPageUp,
+    GoTop,
+    GoBottom,
+    GoLeft,
+    GoRight,
+    ToggleCellMode,
+    ToggleMark,
+    YankCell,
+    YankRow,
+    YankMarked,
+    Filter,
+    ToggleGroup,
+    OpenLink,
+    Retry,
+    ColumnStats,
+    SetMark,
+    JumpToMark,
+    NextMark,
+    PrevMark,
+    Help,
+    Select,
 }
 impl Display for TableCommand {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             TableCommand::GoDown => write!(f, "go down"),
             TableCommand::GoUp => write!(f, "go up"),
+            TableCommand::GoDownFast => write!(f, "go down fast"),
+            TableCommand::GoUpFast => write!(f, "go up fast"),
             TableCommand::GoDownCycle => write!(f, "go down cycle"),
             TableCommand::GoUpCycle => write!(f, "go up cycle"),
             TableCommand::GoPageDown => write!(f, "go page down"),
             TableCommand::GoPageUp => write!(f, "go page up"),
             TableCommand::GoHalfPageDown => write!(f, "go half page down"),
-            // TableCommand::GoHalfPageUp => write!(f, "go half page up"),
+            TableCommand::GoHalfPageUp => write!(f, "go half page up"),
+            TableCommand::GoTop => write!(f, "go top"),
+            TableCommand::GoBottom => write!(f, "go bottom"),
+            TableCommand::GoLeft => write!(f, "go left"),
+            TableCommand::GoRight => write!(f, "go right"),
+            TableCommand::ToggleCellMode => write!(f, "toggle cell mode"),
+            TableCommand::ToggleMark => write!(f, "toggle mark"),
+            TableCommand::YankCell => write!(f, "yank cell"),
+            TableCommand::YankRow => write!(f, "yank row"),
+            TableCommand::YankMarked => write!(f, "yank marked rows"),
+            TableCommand::Filter => write!(f, "filter"),
+            TableCommand::ToggleGroup => write!(f, "toggle group"),
+            TableCommand::OpenLink => write!(f, "open link"),
+            TableCommand::Retry => write!(f, "retry"),
+            TableCommand::ColumnStats => write!(f, "column stats"),
+            TableCommand::SetMark => write!(f, "set mark"),
+            TableCommand::JumpToMark => write!(f, "jump to mark"),
+            TableCommand::NextMark => write!(f, "next mark"),
+            TableCommand::PrevMark => write!(f, "previous mark"),
+            TableCommand::Help => write!(f, "help"),
+            TableCommand::Select => write!(f, "select"),
         }
     }
 }
-
-#[derive(Clone)]
-pub struct TableKeyMap(pub Vec<ShortCut<TableCommand>>);
-impl KeyMap for TableKeyMap {
-    type Command = TableCommand;
-
-    fn get_shortcuts(&self) -> &[ShortCut<Self::Command>] {
-        &self.0
-    }
-    fn default() -> Self {
-        Self(vec![
+impl Command for TableCommand {
+    fn default_bindings() -> Vec<ShortCut<Self>> {
+        vec![
             ShortCut(
                 TableCommand::GoDown,
                 vec![
-                    KeyEvent::new(KeyCode::Down, KeyModifiers::NONE),
-                    KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE),
+                    KeyEvent::new(KeyCode::Down, KeyModifiers::NONE).into(),
+                    KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE).into(),
+                    MouseBinding::new(MouseAction::ScrollDown, KeyModifiers::NONE).into(),
                 ],
             ),
             ShortCut(
                 TableCommand::GoUp,
                 vec![
-                    KeyEvent::new(KeyCode::Up, KeyModifiers::NONE),
-                    KeyEvent::new(KeyCode::Char('k'), KeyModifiers::NONE),
+                    KeyEvent::new(KeyCode::Up, KeyModifiers::NONE).into(),
+                    KeyEvent::new(KeyCode::Char('k'), KeyModifiers::NONE).into(),
+                    MouseBinding::new(MouseAction::ScrollUp, KeyModifiers::NONE).into(),
+                ],
+            ),
+            ShortCut(
+                TableCommand::GoDownFast,
+                vec![MouseBinding::new(MouseAction::ScrollDown, KeyModifiers::ALT).into()],
+            ),
+            ShortCut(
+                TableCommand::GoUpFast,
+                vec![MouseBinding::new(MouseAction::ScrollUp, KeyModifiers::ALT).into()],
+            ),
+            ShortCut(
+                TableCommand::Select,
+                vec![
+                    MouseBinding::new(MouseAction::Click(MouseButton::Left), KeyModifiers::NONE)
+                        .into(),
+                    MouseBinding::new(MouseAction::Click(MouseButton::Right), KeyModifiers::NONE)
+                        .into(),
                 ],
             ),
             ShortCut(
                 TableCommand::GoDownCycle,
-                vec![KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE)],
+                vec![KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE).into()],
             ),
             ShortCut(
                 TableCommand::GoUpCycle,
-                vec![KeyEvent::new(KeyCode::BackTab, KeyModifiers::NONE)],
+                vec![KeyEvent::new(KeyCode::BackTab, KeyModifiers::NONE).into()],
             ),
             ShortCut(
                 TableCommand::GoPageDown,
-                vec![KeyEvent::new(KeyCode::PageDown, KeyModifiers::NONE)],
+                vec![KeyEvent::new(KeyCode::PageDown, KeyModifiers::NONE).into()],
             ),
             ShortCut(
                 TableCommand::GoPageUp,
-                vec![KeyEvent::new(KeyCode::PageUp, KeyModifiers::NONE)],
+                vec![KeyEvent::new(KeyCode::PageUp, KeyModifiers::NONE).into()],
             ),
             ShortCut(
                 TableCommand::GoHalfPageDown,
-                vec![KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE)],
+                vec![KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE).into()],
+            ),
+            ShortCut(
+                TableCommand::GoTop,
+                vec![
+                    KeyEvent::new(KeyCode::Home, KeyModifiers::NONE).into(),
+                    KeySequence(vec![
+                        KeyEvent::new(KeyCode::Char('g'), KeyModifiers::NONE),
+                        KeyEvent::new(KeyCode::Char('g'), KeyModifiers::NONE),
+                    ])
+                    .into(),
+                ],
+            ),
+            ShortCut(
+                TableCommand::GoBottom,
+                vec![
+                    KeyEvent::new(KeyCode::End, KeyModifiers::NONE).into(),
+                    KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT).into(),
+                ],
+            ),
+            ShortCut(
+                TableCommand::GoLeft,
+                vec![
+                    KeyEvent::new(KeyCode::Left, KeyModifiers::NONE).into(),
+                    KeyEvent::new(KeyCode::Char('h'), KeyModifiers::NONE).into(),
+                ],
+            ),
+            ShortCut(
+                TableCommand::GoRight,
+                vec![
+                    KeyEvent::new(KeyCode::Right, KeyModifiers::NONE).into(),
+                    KeyEvent::new(KeyCode::Char('l'), KeyModifiers::NONE).into(),
+                ],
+            ),
+            ShortCut(
+                TableCommand::ToggleCellMode,
+                vec![KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE).into()],
+            ),
+            ShortCut(
+                TableCommand::ToggleMark,
+                vec![KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE).into()],
+            ),
+            ShortCut(
+                TableCommand::YankCell,
+                vec![KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE).into()],
+            ),
+            ShortCut(
+                TableCommand::YankRow,
+                vec![KeyEvent::new(KeyCode::Char('Y'), KeyModifiers::SHIFT).into()],
+            ),
+            ShortCut(
+                TableCommand::YankMarked,
+                vec![KeyEvent::new(KeyCode::Char('y'), KeyModifiers::CONTROL).into()],
+            ),
+            ShortCut(
+                TableCommand::Filter,
+                vec![KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE).into()],
+            ),
+            ShortCut(
+                TableCommand::ToggleGroup,
+                vec![KeyEvent::new(KeyCode::Char('z'), KeyModifiers::NONE).into()],
+            ),
+            ShortCut(
+                TableCommand::OpenLink,
+                vec![KeyEvent::new(KeyCode::Char('o'), KeyModifiers::NONE).into()],
+            ),
+            ShortCut(
+                TableCommand::Retry,
+                vec![KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE).into()],
+            ),
+            ShortCut(
+                TableCommand::ColumnStats,
+                vec![KeyEvent::new(KeyCode::Char('s'), KeyModifiers::NONE).into()],
+            ),
+            ShortCut(
+                TableCommand::SetMark,
+                vec![KeyEvent::new(KeyCode::Char('m'), KeyModifiers::NONE).into()],
+            ),
+            ShortCut(
+                TableCommand::JumpToMark,
+                vec![KeyEvent::new(KeyCode::Char('\''), KeyModifiers::NONE).into()],
+            ),
+            ShortCut(
+                TableCommand::NextMark,
+                vec![KeySequence(vec![
+                    KeyEvent::new(KeyCode::Char(']'), KeyModifiers::NONE),
+                    KeyEvent::new(KeyCode::Char('\''), KeyModifiers::NONE),
+                ])
+                .into()],
             ),
-            // FIXME: crossterm is not detecting the shift ???
-            // ShortCut(
-            //     TableCommand::GoHalfPageUp,
-            //     vec![KeyEvent::new(KeyCode::Char(' '), KeyModifiers::SHIFT)],
-            // ),
-        ])
+            ShortCut(
+                TableCommand::PrevMark,
+                vec![KeySequence(vec![
+                    KeyEvent::new(KeyCode::Char('['), KeyModifiers::NONE),
+                    KeyEvent::new(KeyCode::Char('\''), KeyModifiers::NONE),
+                ])
+                .into()],
+            ),
+            ShortCut(
+                TableCommand::Help,
+                vec![KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE).into()],
+            ),
+        ]
+    }
+    fn description(&self) -> Option<String> {
+        let description = match self {
+            TableCommand::GoDownCycle => "go down, wrapping to the first row",
+            TableCommand::GoUpCycle => "go up, wrapping to the last row",
+            TableCommand::ToggleCellMode => "switch between row and cell selection",
+            TableCommand::ToggleMark => "mark or unmark the selected row",
+            TableCommand::YankCell => "copy the selected cell to the clipboard",
+            TableCommand::YankRow => "copy the selected row to the clipboard",
+            TableCommand::YankMarked => "copy the marked rows to the clipboard",
+            TableCommand::Filter => "filter rows with a query",
+            TableCommand::ToggleGroup => "expand or collapse the selected group",
+            TableCommand::OpenLink => "open the link in the selected cell",
+            TableCommand::Retry => "reload the data",
+            TableCommand::ColumnStats => "show statistics of the selected column",
+            TableCommand::SetMark => "bookmark the selected row under the next key",
+            TableCommand::JumpToMark => "jump to the bookmark named by the next key",
+            TableCommand::NextMark => "jump to the next bookmark",
+            TableCommand::PrevMark => "jump to the previous bookmark",
+            TableCommand::Help => "show this help",
+            TableCommand::GoDownFast => "go down two rows",
+            TableCommand::GoUpFast => "go up two rows",
+            TableCommand::Select => "select the clicked row, or sort by the clicked column",
+            _ => return None,
+        };
+        Some(description.into())
+    }
+}
+
+#[derive(Clone)]
+pub struct TableKeyMap(pub Vec<ShortCut<TableCommand>>);
+impl KeyMap for TableKeyMap {
+    type Command = TableCommand;
+
+    fn get_shortcuts(&self) -> &[ShortCut<Self::Command>] {
+        &self.0
+    }
+    fn default() -> Self {
+        Self(TableCommand::default_bindings())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone)]
+    struct Item(&'static str, u32);
+    impl Tabular for Item {
+        type Value = &'static str;
+
+        fn value(&self) -> Self::Value {
+            self.0
+        }
+        fn content(&self) -> Vec<String> {
+            vec![self.0.to_string(), self.1.to_string()]
+        }
+        fn column_constraints() -> Vec<fn(u16) -> Constraint> {
+            vec![Constraint::Length, Constraint::Length]
+        }
+        fn column_names() -> Option<Vec<String>> {
+            Some(vec!["name".into(), "n".into()])
+        }
+    }
+
+    fn table(items: &[(&'static str, u32)]) -> StatefulTable<'static, Item> {
+        let data = items.iter().map(|(name, n)| Item(name, *n)).collect();
+        StatefulTable::new(data, TableState::new(), TableStyle::default(), None)
+    }
+    fn update(table: &mut StatefulTable<'_, Item>, items: &[(&'static str, u32)]) {
+        table.update_data(items.iter().map(|(name, n)| Item(name, *n)).collect());
+    }
+
+    #[test]
+    fn marks_changed_and_added_rows() {
+        let mut table = table(&[("a", 1), ("b", 2), ("c", 3)]);
+        update(&mut table, &[("a", 1), ("b", 5), ("c", 3), ("d", 4)]);
+        assert!(!table.changes.contains_key(&0));
+        assert!(matches!(&table.changes[&1].0, Change::Cells(cols) if cols == &[1]));
+        assert!(matches!(table.changes[&3].0, Change::Added));
+    }
+
+    #[test]
+    fn keeps_removed_rows_in_place() {
+        let mut table = table(&[("a", 1), ("b", 2), ("c", 3)]);
+        update(&mut table, &[("a", 1), ("c", 3)]);
+        assert!(table.entries == [Entry::Row(0), Entry::Ghost(0), Entry::Row(1)]);
+        assert_eq!(table.values, [Some("a"), None, Some("c")]);
+
+        update(&mut table, &[("c", 3)]);
+        assert!(table.entries == [Entry::Ghost(1), Entry::Ghost(0), Entry::Row(0)]);
+
+        update(&mut table, &[("b", 2), ("c", 3)]);
+        assert_eq!(table.ghosts.len(), 1);
+        assert!(table.changes.contains_key(&0));
+    }
+
+    #[test]
+    fn follows_rows_across_reorders() {
+        let mut table = table(&[("a", 1), ("b", 2), ("c", 3)]);
+        table.select_absolute(0);
+        table.toggle_mark();
+        update(&mut table, &[("c", 3), ("b", 2), ("a", 1)]);
+        assert!(table.changes.is_empty());
+        assert_eq!(table.selected_value(), Some(&"a"));
+        assert_eq!(table.marked_values(), [&"a"]);
+    }
+
+    #[test]
+    fn matches_duplicate_keys_in_order() {
+        let mut table = table(&[("a", 1), ("a", 2), ("b", 3)]);
+        update(&mut table, &[("b", 3), ("a", 2), ("a", 1)]);
+        assert!(table.ghosts.is_empty());
+        assert!(table.changes.is_empty());
+
+        update(&mut table, &[("b", 3), ("a", 5), ("a", 1)]);
+        assert!(table.changes.contains_key(&1));
+        assert!(!table.changes.contains_key(&2));
+    }
+
+    #[test]
+    fn skips_ghosts_when_moving() {
+        let mut table = table(&[("a", 1), ("b", 2), ("c", 3)]);
+        table.select_absolute(0);
+        update(&mut table, &[("a", 1), ("c", 3)]);
+        table.select_next();
+        assert_eq!(table.selected_value(), Some(&"c"));
+        table.select_prev();
+        assert_eq!(table.selected_value(), Some(&"a"));
+    }
+
+    #[test]
+    fn keeps_selection_when_ghosts_and_changes_expire() {
+        let mut table = table(&[("a", 1), ("b", 2), ("c", 3), ("d", 4)]);
+        table.set_change_fade(Duration::from_millis(50));
+        table.select_absolute(2);
+        update(&mut table, &[("a", 1), ("c", 5), ("d", 4)]);
+        assert_eq!(table.selected_value(), Some(&"c"));
+        std::thread::sleep(Duration::from_millis(60));
+        table.expire_changes();
+        assert!(table.ghosts.is_empty());
+        assert_eq!(table.selected_value(), Some(&"c"));
+    }
+
+    #[test]
+    fn skips_ghosts_of_filtered_rows() {
+        let mut table = table(&[("a", 1), ("b", 2), ("c", 3)]);
+        table.set_query("name=a").unwrap();
+        assert_eq!(table.rows_count(), 1);
+        update(&mut table, &[("a", 1), ("c", 3)]);
+        assert_eq!(table.rows_count(), 1);
+
+        table.clear_query();
+        update(&mut table, &[("a", 1)]);
+        assert_eq!(table.rows_count(), 2);
+        table.set_query("name=a").unwrap();
+        assert_eq!(table.rows_count(), 1);
+    }
+
+    #[test]
+    fn keeps_ghosts_in_expanded_groups() {
+        let mut table = table(&[("a", 1), ("b", 1), ("d", 1), ("c", 2)]);
+        table.set_group_by(Some(GroupBy::Key(1)));
+        table.select_absolute(0);
+        table.toggle_group();
+        assert_eq!(table.rows_count(), 3);
+        update(&mut table, &[("a", 1), ("d", 1), ("c", 2)]);
+        assert_eq!(table.rows_count(), 3);
+
+        table.toggle_group();
+        update(&mut table, &[("d", 1), ("c", 2)]);
+        assert!(
+            table.entries
+                == [
+                    Entry::Group(0),
+                    Entry::Ghost(0),
+                    Entry::Row(0),
+                    Entry::Group(1),
+                    Entry::Row(1)
+                ]
+        );
+        table.select_absolute(0);
+        table.toggle_group();
+        assert!(table.ghosts.is_empty());
+        assert_eq!(table.rows_count(), 3);
+    }
+
+    #[test]
+    fn keeps_cells_off_the_index_column() {
+        let data = vec![Item("a", 1), Item("b", 2)];
+        let mut table =
+            StatefulTable::new_indexed(data, TableState::new(), TableStyle::default(), None);
+        table.select_absolute(0);
+        assert_eq!(table.selected_cell_content().as_deref(), Some("a"));
+
+        table.toggle_cell_mode();
+        table.handle_command(&TableCommand::GoRight);
+        table.handle_command_with_count(&TableCommand::GoLeft, Some(5));
+        assert_eq!(table.selected_col(), Some(1));
+        assert_eq!(table.selected_cell_content().as_deref(), Some("a"));
+    }
+
+    #[test]
+    fn counts_collapsed_rows_in_column_stats() {
+        let mut table = table(&[("a", 1), ("b", 1), ("c", 2)]);
+        table.set_query("name!=c").unwrap();
+        table.set_group_by(Some(GroupBy::Key(1)));
+        table.select_absolute(0);
+        table.toggle_group();
+        let stats = table.column_stats(5);
+        assert_eq!(stats.name.as_deref(), Some("name"));
+        assert_eq!(stats.count, 2);
+    }
+
+    #[test]
+    fn keeps_bookmarks_on_updates_only() {
+        let mut table = table(&[("a", 1), ("b", 2), ("c", 3)]);
+        table.select_absolute(1);
+        table.set_bookmark('x');
+        update(&mut table, &[("c", 3), ("b", 2)]);
+        table.select_absolute(0);
+        assert!(table.jump_to_bookmark('x'));
+        assert_eq!(table.selected_value(), Some(&"b"));
+
+        table.set_data(vec![Item("b", 2)]);
+        assert!(!table.jump_to_bookmark('x'));
+    }
+
+    #[test]
+    fn counts_data_rows_for_go_top() {
+        let mut table = table(&[("a", 1), ("b", 2), ("c", 3), ("d", 4)]);
+        update(&mut table, &[("a", 1), ("c", 3), ("d", 4)]);
+        table.handle_command_with_count(&TableCommand::GoTop, Some(2));
+        assert_eq!(table.selected_value(), Some(&"c"));
+        table.handle_command_with_count(&TableCommand::GoBottom, Some(9));
+        assert_eq!(table.selected_value(), Some(&"d"));
+    }
+
+    #[test]
+    fn ignores_clicks_while_loading() {
+        let mut table = table(&[("a", 1), ("b", 2), ("c", 3)]);
+        table.area = Rect::new(0, 0, 20, 10);
+        table.select_absolute(0);
+        table.set_status(TableStatus::Loading);
+        let click = Event::Mouse(ratatui::crossterm::event::MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 1,
+            row: 3,
+            modifiers: KeyModifiers::NONE,
+        });
+        assert!(table.update(&click).is_none());
+        assert_eq!(table.selected_row(), Some(0));
     }
 }
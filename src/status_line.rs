@@ -2,7 +2,7 @@ use std::fmt::Display;
 use std::time::{Duration, Instant};
 
 use itertools::Itertools;
-use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::layout::{Alignment, Constraint, Layout, Rect};
 use ratatui::widgets::Paragraph;
 use ratatui::Frame;
 
@@ -40,6 +40,8 @@ impl Display for Message {
 pub struct StatusLine {
     ids: StatusId,
     lines: Vec<Message>,
+    pending_keys: String,
+    mode: String,
 }
 impl StatusLine {
     pub fn show(
@@ -61,22 +63,37 @@ impl StatusLine {
     pub fn update(&mut self) {
         self.lines.retain(|line| {
             line.duration
-                .map_or(true, |ttl| line.created_at + ttl > Instant::now())
+                .is_none_or(|ttl| line.created_at + ttl > Instant::now())
         });
     }
     pub fn remove(&mut self, id: StatusId) {
         self.lines.retain(|l| l.id != id);
     }
+    pub fn set_pending_keys(&mut self, keys: String) {
+        self.pending_keys = keys;
+    }
+    pub fn set_mode(&mut self, mode: String) {
+        self.mode = mode;
+    }
     pub fn get_layout(&self) -> Layout {
         let layout = Layout::default().direction(ratatui::layout::Direction::Vertical);
-        if self.lines.is_empty() {
+        if self.lines.is_empty() && self.pending_keys.is_empty() && self.mode.is_empty() {
             layout.constraints([Constraint::Fill(1), Constraint::Length(0)])
         } else {
             layout.constraints([Constraint::Fill(1), Constraint::Length(1)])
         }
     }
     pub fn draw(&self, f: &mut Frame, area: Rect) {
-        f.render_widget(Paragraph::new(self.get_line()), area);
+        let line = if self.mode.is_empty() {
+            self.get_line()
+        } else {
+            format!("-- {} -- {}", self.mode.to_uppercase(), self.get_line())
+        };
+        f.render_widget(Paragraph::new(line), area);
+        if !self.pending_keys.is_empty() {
+            let keys = Paragraph::new(self.pending_keys.as_str()).alignment(Alignment::Right);
+            f.render_widget(keys, area);
+        }
     }
 
     pub fn get_line(&self) -> String {
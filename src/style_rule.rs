@@ -0,0 +1,87 @@
+use ratatui::style::Style;
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::config::deserialize_style;
+
+#[derive(Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleScope {
+    #[default]
+    Cell,
+    Row,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Pattern(pub Regex);
+impl TryFrom<String> for Pattern {
+    type Error = regex::Error;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Regex::new(&value).map(Pattern)
+    }
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Condition {
+    Gt(f64),
+    Ge(f64),
+    Lt(f64),
+    Le(f64),
+    Eq(String),
+    Ne(String),
+    Contains(String),
+    Matches(Pattern),
+}
+impl Condition {
+    pub fn matches(&self, content: &str) -> bool {
+        let num = || content.trim().trim_end_matches('%').parse::<f64>().ok();
+        match self {
+            Condition::Gt(val) => num().is_some_and(|n| n > *val),
+            Condition::Ge(val) => num().is_some_and(|n| n >= *val),
+            Condition::Lt(val) => num().is_some_and(|n| n < *val),
+            Condition::Le(val) => num().is_some_and(|n| n <= *val),
+            Condition::Eq(val) => content == val,
+            Condition::Ne(val) => content != val,
+            Condition::Contains(val) => content.contains(val.as_str()),
+            Condition::Matches(pattern) => pattern.0.is_match(content),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize)]
+pub struct StyleRule {
+    #[serde(default)]
+    pub column: Option<usize>,
+    pub when: Condition,
+    #[serde(deserialize_with = "deserialize_style")]
+    pub style: Style,
+    #[serde(default)]
+    pub scope: RuleScope,
+}
+impl StyleRule {
+    pub fn cell(column: Option<usize>, when: Condition, style: Style) -> Self {
+        Self {
+            column,
+            when,
+            style,
+            scope: RuleScope::Cell,
+        }
+    }
+    pub fn row(column: Option<usize>, when: Condition, style: Style) -> Self {
+        Self {
+            column,
+            when,
+            style,
+            scope: RuleScope::Row,
+        }
+    }
+    pub fn matches_cell(&self, content: &[String], col: usize) -> bool {
+        self.column.is_none_or(|c| c == col)
+            && content.get(col).is_some_and(|c| self.when.matches(c))
+    }
+    pub fn matches_row(&self, content: &[String]) -> bool {
+        (0..content.len()).any(|col| self.matches_cell(content, col))
+    }
+}
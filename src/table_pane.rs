@@ -0,0 +1,89 @@
+use ratatui::crossterm::event::{Event, KeyEvent};
+
+use crate::help::{help_entries, HelpEntry};
+use crate::keymap::{Command, CommandKeyMap, KeyMap, Mode};
+use crate::stateful_table::{StatefulTable, TableCommand, Tabular};
+use crate::view::KeyHandler;
+use crate::which_key::KeyHints;
+
+pub type TableCommandFn<'a, S> = Box<dyn Fn(TableCommand) -> S + 'a>;
+
+// a table with the bindings of the view built around it, the view's own
+// commands come first and the rest goes to the table
+pub struct TablePane<'a, T: Tabular, C: Command, S> {
+    table: StatefulTable<'a, T>,
+    section: String,
+    keymap: CommandKeyMap<C>,
+    on_table_command: Option<TableCommandFn<'a, S>>,
+}
+impl<'a, T: Tabular, C: Command, S> TablePane<'a, T, C, S> {
+    pub fn new(table: StatefulTable<'a, T>, section: &str) -> Self {
+        Self {
+            table,
+            section: section.to_string(),
+            keymap: KeyMap::default(),
+            on_table_command: None,
+        }
+    }
+    pub fn table(&self) -> &StatefulTable<'a, T> {
+        &self.table
+    }
+    pub fn table_mut(&mut self) -> &mut StatefulTable<'a, T> {
+        &mut self.table
+    }
+    pub fn keymap(&self) -> &CommandKeyMap<C> {
+        &self.keymap
+    }
+    pub fn set_keymap(&mut self, keymap: &impl KeyMap<Command = C>) {
+        self.keymap = CommandKeyMap(keymap.get_shortcuts().to_vec());
+    }
+    // the commands the table leaves to the app, like `Filter` or `Help`, as signals
+    pub fn set_on_table_command(&mut self, on_table_command: impl Fn(TableCommand) -> S + 'a) {
+        self.on_table_command = Some(Box::new(on_table_command));
+    }
+    // keys finishing a pending sequence of the table, like the mark name
+    // after `m`, never run the view's own commands
+    pub fn command(&self, ev: &Event) -> Option<C> {
+        match ev {
+            Event::Key(ev) if !self.table.is_pending() => self.keymap.get_command(ev).cloned(),
+            _ => None,
+        }
+    }
+}
+impl<T: Tabular, C: Command, S: Default> TablePane<'_, T, C, S> {
+    pub fn update(&mut self, ev: &Event) -> S {
+        let cmd = self.table.update(ev);
+        self.signal(cmd)
+    }
+    pub fn tick(&mut self) -> S {
+        let cmd = self.table.tick();
+        self.signal(cmd)
+    }
+
+    // --- helpers
+    fn signal(&self, cmd: Option<TableCommand>) -> S {
+        match (cmd, &self.on_table_command) {
+            (Some(cmd), Some(on_table_command)) => on_table_command(cmd),
+            _ => S::default(),
+        }
+    }
+}
+impl<T: Tabular, C: Command, S> KeyHandler for TablePane<'_, T, C, S> {
+    fn captures(&self, ev: &KeyEvent) -> bool {
+        self.table.captures(ev) || self.keymap.get_command(ev).is_some()
+    }
+    fn modes(&self) -> Option<Vec<Mode>> {
+        self.table.modes()
+    }
+    fn help(&self) -> Vec<HelpEntry> {
+        let mut entries = help_entries(&self.section, &self.keymap);
+        entries.extend(self.table.help());
+        entries
+    }
+    fn key_hints(&self) -> Option<KeyHints> {
+        self.table.key_hints()
+    }
+    fn pending_keys(&self) -> String {
+        self.table.pending_keys()
+    }
+}
@@ -0,0 +1,45 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Alignment, Constraint, Layout, Rect};
+use ratatui::style::Style;
+use ratatui::text::{Line, Text};
+use ratatui::widgets::{Clear, Paragraph, Widget, Wrap};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct TooSmall {
+    pub width: u16,
+    pub height: u16,
+    pub style: Style,
+}
+impl TooSmall {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            style: Style::default(),
+        }
+    }
+    pub fn fits(&self, area: Rect) -> bool {
+        area.width >= self.width && area.height >= self.height
+    }
+}
+impl Widget for TooSmall {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(buf.area);
+        Clear.render(area, buf);
+        let text = Text::from(vec![
+            Line::from("Terminal too small"),
+            Line::from(format!(
+                "{}x{} (min {}x{})",
+                area.width, area.height, self.width, self.height
+            )),
+        ]);
+        let [area] = Layout::vertical([Constraint::Length(text.height() as u16)])
+            .flex(ratatui::layout::Flex::Center)
+            .areas(area);
+        Paragraph::new(text)
+            .style(self.style)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .render(area, buf);
+    }
+}
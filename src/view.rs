@@ -1,8 +1,12 @@
-use crossterm::event::Event;
+use crossterm::event::{Event, KeyEvent};
 use ratatui::crossterm::terminal;
 use ratatui::layout::Rect;
 use ratatui::{crossterm, Frame};
 
+use crate::help::HelpEntry;
+use crate::keymap::Mode;
+use crate::which_key::KeyHints;
+
 pub trait View {
     type Model;
     type Signal: Default;
@@ -15,6 +19,29 @@ pub trait View {
     fn is_floating(&self) -> bool {
         false
     }
+    // the table or pane the key related methods below forward to
+    fn key_handler(&self) -> Option<&dyn KeyHandler> {
+        None
+    }
+    // keys the view handles ahead of the mode bindings, like the `Esc` ending a sequence
+    fn captures(&self, ev: &KeyEvent) -> bool {
+        self.key_handler().is_some_and(|k| k.captures(ev))
+    }
+    // `None` responds in every mode
+    fn modes(&self) -> Option<Vec<Mode>> {
+        self.key_handler()?.modes()
+    }
+    fn help(&self) -> Vec<HelpEntry> {
+        self.key_handler().map_or(vec![], |k| k.help())
+    }
+    fn key_hints(&self) -> Option<KeyHints> {
+        self.key_handler()?.key_hints()
+    }
+    // the keys of an unfinished sequence, shown in the status line
+    fn pending_keys(&self) -> String {
+        self.key_handler()
+            .map_or(String::new(), |k| k.pending_keys())
+    }
     fn title(&self) -> String {
         String::new()
     }
@@ -28,6 +55,10 @@ pub trait View {
     fn update(&mut self, _ev: &Event) -> Self::Signal {
         Self::Signal::default()
     }
+    // called with every iteration of the event loop, with or without an event
+    fn tick(&mut self) -> Self::Signal {
+        Self::Signal::default()
+    }
     fn on_prompt_submit(&mut self, _value: String) -> Self::Signal {
         Self::Signal::default()
     }
@@ -35,3 +66,11 @@ pub trait View {
         Self::Signal::default()
     }
 }
+
+pub trait KeyHandler {
+    fn captures(&self, ev: &KeyEvent) -> bool;
+    fn modes(&self) -> Option<Vec<Mode>>;
+    fn help(&self) -> Vec<HelpEntry>;
+    fn key_hints(&self) -> Option<KeyHints>;
+    fn pending_keys(&self) -> String;
+}
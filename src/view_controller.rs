@@ -1,14 +1,22 @@
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
-use ratatui::crossterm::event::Event;
+use ratatui::crossterm::event::{Event, KeyCode, KeyEvent};
 use ratatui::layout::Rect;
 use ratatui::widgets::Clear;
 use ratatui::Frame;
 
 use crate::dock::{Dock, DockPosition};
+use crate::help::{help_entries, HelpEntry};
+use crate::keyboard;
+use crate::keymap::{KeyIssue, KeyMap, ModalKeyMap, Mode, ModeCommand};
 use crate::status_line::{StatusId, StatusLine};
+use crate::too_small::TooSmall;
 use crate::view::View;
+use crate::which_key::WhichKey;
+
+pub type HelpViewFn<M, S, K> =
+    Box<dyn Fn(Vec<HelpEntry>) -> Box<dyn View<Model = M, Signal = S, Kind = K>>>;
 
 pub struct ViewController<M, S, K>
 where
@@ -19,6 +27,14 @@ where
     status: Arc<Mutex<StatusLine>>,
     status_ttl: Duration,
     dock: Option<Dock<M, S, K>>,
+    too_small: Option<TooSmall>,
+    modes: Vec<Mode>,
+    mode_keymap: ModalKeyMap<ModeCommand>,
+    global_help: Vec<HelpEntry>,
+    help_view: Option<(KeyEvent, HelpViewFn<M, S, K>)>,
+    // the number of views while the help is on top
+    help_depth: Option<usize>,
+    which_key: Option<WhichKey<'static>>,
 }
 impl<M, S, K: PartialEq> ViewController<M, S, K>
 where
@@ -31,14 +47,26 @@ where
             status: Default::default(),
             status_ttl,
             dock: Default::default(),
+            too_small: None,
+            modes: vec![Mode::Normal],
+            mode_keymap: KeyMap::default(),
+            global_help: vec![],
+            help_view: None,
+            help_depth: None,
+            which_key: None,
         }
     }
     pub fn draw(&mut self, f: &mut Frame<'_>, area: Rect) {
         if !self.is_running() {
             return;
         }
+        if let Some(too_small) = self.too_small.filter(|t| !t.fits(area)) {
+            f.render_widget(too_small, area);
+            return;
+        }
 
-        let status = self.status.lock().unwrap();
+        let mut status = self.status.lock().unwrap();
+        status.set_pending_keys(self.curr().pending_keys());
         let layout = status.get_layout().split(area);
         status.draw(f, layout[1]);
         drop(status);
@@ -58,6 +86,49 @@ where
         } else {
             self.draw_visible_views(f, layout[0], self.views.len() - 1);
         }
+
+        let hints = self.curr().key_hints();
+        if let Some(which_key) = &mut self.which_key {
+            which_key.set_hints(hints);
+            which_key.draw(f, layout[0]);
+        }
+    }
+    // a view responding in the mode gets the keys it captures, mode bindings come next
+    pub fn update(&mut self, ev: &Event) -> S {
+        if let Event::Key(key) = ev {
+            if self.toggle_help(key) {
+                return S::default();
+            }
+            let captured =
+                self.is_running() && self.responds_in_mode() && self.curr().captures(key);
+            if let Some(cmd) = self
+                .mode_keymap
+                .get_command(key)
+                .filter(|_| !captured)
+                .cloned()
+            {
+                match cmd {
+                    ModeCommand::Push(mode) => self.push_mode(mode),
+                    ModeCommand::Pop => self.pop_mode(),
+                    ModeCommand::Set(mode) => self.set_mode(mode),
+                }
+                return S::default();
+            }
+        }
+        if !self.is_running() {
+            return S::default();
+        }
+        if self.responds_in_mode() {
+            self.curr_mut().update(ev)
+        } else {
+            S::default()
+        }
+    }
+    pub fn tick(&mut self) -> S {
+        if !self.is_running() {
+            return S::default();
+        }
+        self.curr_mut().tick()
     }
     pub fn is_running(&self) -> bool {
         !self.views.is_empty()
@@ -75,6 +146,12 @@ where
     }
     pub fn pop(&mut self) {
         self.views.pop();
+        if self
+            .help_depth
+            .is_some_and(|depth| depth > self.views.len())
+        {
+            self.help_depth = None;
+        }
         if self.is_running() {
             self.curr().set_title();
         }
@@ -92,7 +169,7 @@ where
         self.refresh_visible_views(model, self.views.len() - 1);
     }
     fn refresh_visible_views(&mut self, model: &M, idx: usize) {
-        if self.views[idx].is_floating() {
+        if self.views[idx].is_floating() && idx > 0 {
             self.refresh_visible_views(model, idx - 1);
             self.views[idx].refresh(model);
         } else {
@@ -101,12 +178,16 @@ where
     }
     fn draw_visible_views(&mut self, f: &mut Frame<'_>, area: Rect, idx: usize) {
         if self.views[idx].is_floating() {
-            self.draw_visible_views(f, area, idx - 1);
+            if idx > 0 {
+                self.draw_visible_views(f, area, idx - 1);
+            }
             let view = &mut self.views[idx];
-            let area = view.compute_area(area);
-            f.render_widget(Clear, area);
-            view.draw(f, area);
-        } else {
+            let area = view.compute_area(area).intersection(area);
+            if !area.is_empty() {
+                f.render_widget(Clear, area);
+                view.draw(f, area);
+            }
+        } else if !area.is_empty() {
             self.views[idx].draw(f, area);
         }
     }
@@ -128,10 +209,106 @@ where
     pub fn show_status_always(&self, msg: String) -> StatusId {
         self.status.lock().unwrap().show(msg, None, true)
     }
+    // e.g. the result of `KeyMap::validate` on the user config at startup
+    pub fn show_key_issues(&self, issues: &[KeyIssue]) {
+        let msg = match issues {
+            [] => return,
+            [issue] => format!("key bindings: {issue}"),
+            [issue, rest @ ..] => format!("key bindings: {issue} (and {} more)", rest.len()),
+        };
+        self.show_status(msg);
+    }
     pub fn update_status_line(&self) {
         self.status.lock().unwrap().update();
     }
 
+    // --- modes
+    pub fn mode(&self) -> &Mode {
+        self.modes.last().unwrap()
+    }
+    pub fn push_mode(&mut self, mode: Mode) {
+        self.modes.push(mode);
+        self.refresh_mode();
+    }
+    pub fn pop_mode(&mut self) {
+        if self.modes.len() > 1 {
+            self.modes.pop();
+        }
+        self.refresh_mode();
+    }
+    pub fn set_mode(&mut self, mode: Mode) {
+        *self.modes.last_mut().unwrap() = mode;
+        self.refresh_mode();
+    }
+    pub fn set_mode_keymap(&mut self, keymap: ModalKeyMap<ModeCommand>) {
+        self.mode_keymap = keymap;
+        self.refresh_mode();
+    }
+    fn refresh_mode(&mut self) {
+        let mode = self.mode().clone();
+        let label = match mode {
+            Mode::Normal => String::new(),
+            ref mode => mode.to_string(),
+        };
+        self.status.lock().unwrap().set_mode(label);
+        self.mode_keymap.set_mode(mode);
+    }
+
+    // --- help
+    pub fn set_global_help(&mut self, entries: Vec<HelpEntry>) {
+        self.global_help = entries;
+    }
+    // the current view first, then the dock, the mode bindings and the app-wide bindings
+    pub fn help(&self) -> Vec<HelpEntry> {
+        let mut entries = vec![];
+        if self.is_running() {
+            entries.extend(self.curr().help());
+        }
+        if let Some(dock) = &self.dock {
+            entries.extend(dock.view.help().into_iter().map(|entry| HelpEntry {
+                section: format!("Dock: {}", entry.section),
+                ..entry
+            }));
+        }
+        entries.extend(help_entries("Modes", &self.mode_keymap));
+        entries.extend(self.global_help.iter().cloned());
+        entries
+    }
+
+    // `key` opens a view of `help()` built by `help_view`, the key or `Esc` closes it
+    pub fn set_help_view(
+        &mut self,
+        key: KeyEvent,
+        help_view: impl Fn(Vec<HelpEntry>) -> Box<dyn View<Model = M, Signal = S, Kind = K>> + 'static,
+    ) {
+        self.help_view = Some((key, Box::new(help_view)));
+    }
+    pub fn remove_help_view(&mut self) {
+        self.help_view = None;
+    }
+    pub fn is_help_visible(&self) -> bool {
+        self.help_depth == Some(self.views.len())
+    }
+
+    // --- which key
+    pub fn set_which_key(&mut self, which_key: WhichKey<'static>) {
+        self.which_key = Some(which_key);
+    }
+    pub fn remove_which_key(&mut self) {
+        self.which_key = None;
+    }
+
+    // --- too small
+    pub fn set_min_size(&mut self, width: u16, height: u16) {
+        self.too_small = Some(TooSmall::new(width, height));
+    }
+    pub fn set_too_small(&mut self, too_small: TooSmall) {
+        self.too_small = Some(too_small);
+    }
+    pub fn remove_min_size(&mut self) {
+        self.too_small = None;
+    }
+
     // --- dock
     pub fn set_dock(&mut self, dock: Dock<M, S, K>) {
         self.dock = Some(dock);
@@ -144,4 +321,37 @@ where
             .as_mut()
             .map_or(S::default(), |dock| dock.view.update(ev))
     }
+
+    // --- helpers
+    fn responds_in_mode(&self) -> bool {
+        let mode = self.mode();
+        self.curr().modes().is_none_or(|modes| modes.contains(mode))
+    }
+    fn toggle_help(&mut self, key: &KeyEvent) -> bool {
+        let Some((help_key, _)) = &self.help_view else {
+            return false;
+        };
+        if !self.is_running() || keyboard::is_release(key) {
+            return false;
+        }
+        let is_help_key = keyboard::normalize(key) == keyboard::normalize(help_key);
+        if self.is_help_visible() {
+            if is_help_key || key.code == KeyCode::Esc {
+                self.pop();
+                return true;
+            }
+            return false;
+        }
+        // the key may finish a sequence or be typed in a mode the view ignores
+        if !is_help_key || !self.curr().pending_keys().is_empty() || !self.responds_in_mode() {
+            return false;
+        }
+        let entries = self.help();
+        if let Some((_, help_view)) = &self.help_view {
+            let view = help_view(entries);
+            self.push(view);
+            self.help_depth = Some(self.views.len());
+        }
+        true
+    }
 }
@@ -0,0 +1,102 @@
+use ratatui::crossterm::event::KeyEvent;
+use ratatui::layout::{Alignment, Constraint, Rect};
+use ratatui::widgets::{Clear, TableState};
+use ratatui::Frame;
+
+use crate::keymap::{KeyMap, KeySequence};
+use crate::stateful_table::{StatefulTable, TableStyle, Tabular};
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct KeyHint {
+    pub keys: String,
+    pub command: String,
+}
+impl Tabular for KeyHint {
+    type Value = ();
+    fn value(&self) -> Self::Value {}
+    fn content(&self) -> Vec<String> {
+        vec![self.keys.clone(), self.command.clone()]
+    }
+    fn column_constraints() -> Vec<fn(u16) -> Constraint> {
+        vec![Constraint::Length, Constraint::Fill]
+    }
+    fn column_names() -> Option<Vec<String>> {
+        Some(vec!["Key".to_string(), "Command".to_string()])
+    }
+    fn column_alignments() -> Option<Vec<Alignment>> {
+        Some(vec![Alignment::Left, Alignment::Left])
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct KeyHints {
+    pub prefix: String,
+    pub hints: Vec<KeyHint>,
+}
+impl KeyHints {
+    pub fn new<K: KeyMap>(keymap: &K, prefix: &[KeyEvent]) -> Self {
+        let hints = keymap
+            .continuations(prefix)
+            .into_iter()
+            .map(|(keys, cmd)| KeyHint {
+                keys: keys.to_string(),
+                command: cmd.to_string(),
+            })
+            .collect();
+        Self {
+            prefix: KeySequence(prefix.to_vec()).to_string(),
+            hints,
+        }
+    }
+}
+
+pub struct WhichKey<'a> {
+    table: StatefulTable<'a, KeyHint>,
+    hints: Option<KeyHints>,
+}
+impl<'a> WhichKey<'a> {
+    pub fn new(style: TableStyle<'a>) -> Self {
+        let mut table = StatefulTable::new(vec![], TableState::new(), style, None);
+        table.set_hyperlinks(false);
+        Self { table, hints: None }
+    }
+    pub fn hints(&self) -> Option<&KeyHints> {
+        self.hints.as_ref()
+    }
+    pub fn set_hints(&mut self, hints: Option<KeyHints>) {
+        if hints == self.hints {
+            return;
+        }
+        if let Some(hints) = &hints {
+            self.table.set_data(hints.hints.clone());
+            self.table.set_title(Some(hints.prefix.clone()));
+        }
+        self.hints = hints;
+    }
+    pub fn is_visible(&self) -> bool {
+        self.hints.as_ref().is_some_and(|h| !h.hints.is_empty())
+    }
+    // bottom right corner, right above the status line
+    pub fn compute_area(&self, area: Rect) -> Rect {
+        let (width, height) = self.table.min_area();
+        let width = width.max(30).min(area.width);
+        let height = height.min(area.height);
+        Rect {
+            x: area.right() - width,
+            y: area.bottom() - height,
+            width,
+            height,
+        }
+    }
+    pub fn draw(&mut self, f: &mut Frame<'_>, area: Rect) {
+        if !self.is_visible() {
+            return;
+        }
+        let area = self.compute_area(area);
+        if area.is_empty() {
+            return;
+        }
+        f.render_widget(Clear, area);
+        self.table.draw(f, area);
+    }
+}